@@ -1,83 +1,376 @@
-use std::cmp::{Ordering, Reverse};
-use chrono::{Local};
-use rand::Rng;
-use ratatui::widgets::ListState;
+use std::cmp::Reverse;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::mpsc::TryRecvError;
+use std::time::Instant;
+use chrono::{Duration, Local};
+use ratatui::widgets::{ListState, TableState};
+use crate::daily_challenge::{daily_challenge_seed, today, DAILY_CHALLENGE_MAX_NUMBER, DAILY_CHALLENGE_MIN_NUMBER};
+use crate::export::{export_scores, ExportFormat};
+use crate::game::{parse_guess, validate_range, Game, GuessError, GuessOutcome};
 use crate::models::current_screen::CurrentScreen;
-use crate::models::game_info::GameInfo;
-use crate::models::game_move::GameMove;
+use crate::models::custom_game_field::CustomGameField;
+use crate::models::custom_game_info::CustomGameInfo;
+use crate::models::game_rules::GameRules;
+use crate::models::hot_seat_setup_info::HotSeatSetupInfo;
+use crate::models::leaderboard_info::LeaderboardInfo;
+use crate::models::leaderboard_sort_key::LeaderboardSortKey;
+use crate::models::menu_item::MenuItem;
+use crate::models::mode_filter::ModeFilter;
+use crate::models::profile::Profile;
+use crate::models::profile_edit::ProfileEdit;
+use crate::models::profiles_info::ProfilesInfo;
+use crate::models::race_answer::RaceAnswer;
+use crate::models::race_info::RaceInfo;
+use crate::models::race_player::RacePlayer;
+use crate::models::race_player_state::RacePlayerState;
+use crate::models::replay_info::ReplayInfo;
+use crate::models::saved_game::SavedGame;
+use crate::models::score::Score;
 use crate::models::user_input_info::UserInputInfo;
 use crate::models::user_input_mode::UserInputMode;
-use crate::scores::add_score;
+use crate::profiles::{name_key, normalize_name, PlayerProfiles};
+use crate::race::client::RaceClient;
+use crate::race::protocol::{ClientMessage, HostMessage};
+use crate::saves::{load_game, remove_saved_game, save_game};
+use crate::scoring::range_sort_key;
+use crate::scores::{create_score, moved_aside_message, read_score_file, ScoreQuery, ScoreStore};
+
+const PLAYER_NAME_MAX_LEN: usize = 32;
+const HOT_SEAT_MIN_PLAYERS: usize = 2;
+const HOT_SEAT_MAX_PLAYERS: usize = 8;
+const CUSTOM_GAME_INPUT_MAX_LEN: usize = 10;
 
 pub struct App {
     pub current_screen: CurrentScreen,
     pub main_menu_item_selected: ListState,
-    pub main_menu_items: Vec<String>,
-    pub game_info: GameInfo,
-    pub user_input_history: Vec<GameMove>,
+    pub main_menu_items: Vec<MenuItem>,
+    pub custom_game_info: CustomGameInfo,
+    pub game: Game,
+    pub guess_response: String,
+    pub guess_error: Option<GuessError>,
     pub quit_confirm_popup: bool,
     pub user_input_info: UserInputInfo,
-    pub user_name: String,
     pub mode: UserInputMode,
+    pub seed: Option<u64>,
+    pub leaderboard: LeaderboardInfo,
+    pub replay: Option<ReplayInfo>,
+    pub saved_game: Option<SavedGame>,
+    pub profiles: PlayerProfiles,
+    pub profiles_info: ProfilesInfo,
+    pub race: Option<RaceInfo>,
+    pub hot_seat_setup: HotSeatSetupInfo,
+    pub store: Box<dyn ScoreStore>,
+    pub error_banner: Option<String>,
 }
 
 impl App {
-    pub fn new() -> App {
-        App {
+    /// `profiles_error` is shown in the banner when the profiles could not be loaded.
+    pub fn new(seed: Option<u64>, store: Box<dyn ScoreStore>, profiles: PlayerProfiles, profiles_error: Option<String>) -> App {
+        let (saved_game, error_banner) = match load_game() {
+            Ok(saved_game) => (saved_game, profiles_error),
+            Err(err) => (None, Some(format!("Could not load the saved game: {err}"))),
+        };
+
+        let mut app = App {
             current_screen: CurrentScreen::Menu,
             quit_confirm_popup: false,
-            user_input_history: Vec::<GameMove>::new(),
-            user_name: String::new(),
-            game_info: GameInfo {
-                min_number: 0,
-                max_number: 0,
-                generated_number: 0,
-                current_guess_response: String::new(),
-                is_game_over: false,
-                game_started_at: Local::now(),
-                game_completed_at: Local::now(),
+            game: Game::new(1..=100, GameRules::default(), &mut rand::thread_rng()),
+            guess_response: String::new(),
+            guess_error: None,
+            main_menu_item_selected: ListState::default().with_selected(Some(0)),
+            main_menu_items: Vec::new(),
+            custom_game_info: CustomGameInfo {
+                min_input: "1".to_string(),
+                max_input: "100".to_string(),
+                max_guesses_input: String::new(),
+                time_limit_input: String::new(),
                 is_hard_mode: false,
+                is_invalid_guess_counted: false,
+                selected_field: CustomGameField::MinNumber,
+                error: None,
             },
-            main_menu_item_selected: ListState::default().with_selected(Some(0)),
-            main_menu_items: vec![
-                "Start game (1-100)".to_string(),
-                "Start game (1-100) Hard Mode".to_string(),
-                "Start game (1-1000)".to_string(),
-                "Start game (1-1000) Hard Mode".to_string(),
-                "Start game (1-1000000) Hard Mode".to_string(),
-                "Leaderboard".to_string(),
-                "Quit".to_string(),
-            ],
             user_input_info: UserInputInfo {
                 character_index: 0,
                 input: String::new(),
             },
             mode: UserInputMode::InputNumber,
-        }
+            seed,
+            leaderboard: LeaderboardInfo {
+                loaded_scores: Vec::new(),
+                scores: Vec::new(),
+                available_ranges: Vec::new(),
+                range_filter: None,
+                mode_filter: ModeFilter::All,
+                is_daily_only: false,
+                sort_key: LeaderboardSortKey::Points,
+                table_state: TableState::default(),
+                is_export_popup: false,
+                import_path_input: None,
+                status: None,
+            },
+            replay: None,
+            saved_game,
+            profiles,
+            profiles_info: ProfilesInfo {
+                list_state: ListState::default(),
+                edit: None,
+                input: String::new(),
+                error: None,
+            },
+            race: None,
+            hot_seat_setup: HotSeatSetupInfo {
+                players: Vec::new(),
+                input: String::new(),
+                error: None,
+            },
+            store,
+            error_banner,
+        };
+
+        app.refresh_main_menu();
+        app
+    }
+
+    fn refresh_main_menu(&mut self) {
+        let resume_item = self.saved_game.as_ref().map(|x| MenuItem::ResumeGame {
+            min_number: x.game.info.min_number,
+            max_number: x.game.info.max_number,
+            tries: x.game.tries(),
+        });
+
+        self.main_menu_items = resume_item.into_iter().chain([
+            MenuItem::QuickGame,
+            MenuItem::CustomGame,
+            MenuItem::DailyChallenge,
+            MenuItem::HotSeat,
+            MenuItem::Players { active_name: self.profiles.active().map(|x| x.name.clone()) },
+            MenuItem::Leaderboard,
+            MenuItem::Quit,
+        ]).collect();
+        self.main_menu_item_selected.select(Some(0));
     }
     
     pub fn get_selected_menu_idx(&self) -> usize {
         self.main_menu_item_selected.selected().unwrap_or(0)
     }
 
-    pub fn start_game(&mut self, min_number: i32, max_number: i32, is_hard_mode: bool) {
-        self.game_info.generated_number = rand::thread_rng()
-            .gen_range(min_number..=max_number);
+    pub fn get_selected_menu_item(&self) -> &MenuItem {
+        &self.main_menu_items[self.get_selected_menu_idx()]
+    }
+
+    pub fn open_custom_game(&mut self) {
+        self.custom_game_info.selected_field = CustomGameField::MinNumber;
+        self.custom_game_info.error = None;
+        self.current_screen = CurrentScreen::CustomGame;
+    }
+
+    pub fn custom_game_enter_char(&mut self, value: char) {
+        if !value.is_ascii_digit() {
+            return;
+        }
+
+        let field = match self.custom_game_info.selected_field {
+            CustomGameField::MinNumber => &mut self.custom_game_info.min_input,
+            CustomGameField::MaxNumber => &mut self.custom_game_info.max_input,
+            CustomGameField::MaxGuesses => &mut self.custom_game_info.max_guesses_input,
+            CustomGameField::TimeLimit => &mut self.custom_game_info.time_limit_input,
+            _ => return,
+        };
+
+        if field.len() < CUSTOM_GAME_INPUT_MAX_LEN {
+            field.push(value);
+        }
+        self.custom_game_info.error = None;
+    }
+
+    pub fn custom_game_delete_char(&mut self) {
+        match self.custom_game_info.selected_field {
+            CustomGameField::MinNumber => { self.custom_game_info.min_input.pop(); }
+            CustomGameField::MaxNumber => { self.custom_game_info.max_input.pop(); }
+            CustomGameField::MaxGuesses => { self.custom_game_info.max_guesses_input.pop(); }
+            CustomGameField::TimeLimit => { self.custom_game_info.time_limit_input.pop(); }
+            _ => {}
+        }
+        self.custom_game_info.error = None;
+    }
+
+    pub fn custom_game_submit(&mut self) {
+        let range = parse_range_bound("Min number", &self.custom_game_info.min_input)
+            .and_then(|min| parse_range_bound("Max number", &self.custom_game_info.max_input)
+                .map(|max| (min, max)));
+        let rules = parse_rule_limit("Max guesses", &self.custom_game_info.max_guesses_input)
+            .and_then(|max_guesses| parse_rule_limit("Time limit", &self.custom_game_info.time_limit_input)
+                .map(|time_limit_secs| GameRules {
+                    is_hard_mode: self.custom_game_info.is_hard_mode,
+                    max_guesses,
+                    time_limit_secs,
+                    count_invalid_guesses: self.custom_game_info.is_invalid_guess_counted,
+                }));
+
+        let settings = range
+            .and_then(|(min, max)| validate_range(min, max).map(|_| (min, max)))
+            .and_then(|range| rules.map(|rules| (range, rules)));
+
+        match settings {
+            Ok(((min, max), rules)) => {
+                self.current_screen = CurrentScreen::Game;
+                self.start_game(min, max, rules);
+            }
+            Err(error) => {
+                self.custom_game_info.error = Some(error);
+            }
+        }
+    }
+
+    pub fn start_game(&mut self, min_number: i32, max_number: i32, rules: GameRules) {
+        self.reset_game(Game::from_optional_seed(min_number..=max_number, rules, self.seed));
+    }
+
+    pub fn start_daily_challenge(&mut self) {
+        let date = today();
+        let mut game = Game::from_seed(DAILY_CHALLENGE_MIN_NUMBER..=DAILY_CHALLENGE_MAX_NUMBER,
+                                       GameRules::default(), daily_challenge_seed(date));
+        game.info.daily_challenge = Some(date);
+
+        self.reset_game(game);
+    }
+
+    pub fn open_hot_seat_setup(&mut self) {
+        if self.hot_seat_setup.players.is_empty() {
+            self.hot_seat_setup.players.extend(self.profiles.active().map(|x| x.name.clone()));
+        }
+
+        self.hot_seat_setup.input.clear();
+        self.hot_seat_setup.error = None;
+        self.current_screen = CurrentScreen::HotSeatSetup;
+    }
+
+    pub fn hot_seat_enter_char(&mut self, value: char) {
+        if self.hot_seat_setup.input.chars().count() < PLAYER_NAME_MAX_LEN {
+            self.hot_seat_setup.input.push(value);
+        }
+        self.hot_seat_setup.error = None;
+    }
+
+    /// Removes the last player once the name being typed is empty.
+    pub fn hot_seat_delete_char(&mut self) {
+        if self.hot_seat_setup.input.pop().is_none() {
+            self.hot_seat_setup.players.pop();
+        }
+        self.hot_seat_setup.error = None;
+    }
+
+    /// Adds the typed player, or starts the game when nothing is typed.
+    pub fn hot_seat_submit(&mut self) {
+        let name = normalize_name(&self.hot_seat_setup.input);
+        let players = &mut self.hot_seat_setup.players;
+
+        let result = if name.is_empty() {
+            if players.len() < HOT_SEAT_MIN_PLAYERS {
+                Err(format!("Add at least {HOT_SEAT_MIN_PLAYERS} players"))
+            } else {
+                let mut game = Game::from_optional_seed(1..=100, GameRules::default(), self.seed);
+                game.info.players = players.clone();
+
+                self.reset_game(game);
+                self.current_screen = CurrentScreen::Game;
+                Ok(())
+            }
+        } else if players.len() >= HOT_SEAT_MAX_PLAYERS {
+            Err(format!("A game can have at most {HOT_SEAT_MAX_PLAYERS} players"))
+        } else if players.iter().any(|x| name_key(x) == name_key(&name)) {
+            Err(format!("'{name}' is already playing"))
+        } else {
+            players.push(name);
+            self.hot_seat_setup.input.clear();
+            Ok(())
+        };
+
+        self.hot_seat_setup.error = result.err();
+    }
+
+    fn reset_game(&mut self, game: Game) {
+        self.game = game;
 
         self.user_input_info.input.clear();
         self.input_reset_cursor();
 
-        self.user_input_history.clear();
-
-        self.game_info.game_started_at = Local::now();
-        self.game_info.is_game_over = false;
-        self.game_info.current_guess_response = String::new();
-        self.game_info.min_number = min_number;
-        self.game_info.max_number = max_number;
-        self.game_info.is_hard_mode = is_hard_mode;
+        self.guess_response = String::new();
+        self.guess_error = None;
         self.mode = UserInputMode::InputNumber;
     }
 
+    pub fn resume_game(&mut self) {
+        let Some(SavedGame { mut game, saved_at }) = self.saved_game.take() else {
+            return;
+        };
+
+        game.resume_after((Local::now() - saved_at).max(Duration::zero()));
+
+        self.reset_game(game);
+        self.current_screen = CurrentScreen::Game;
+        self.refresh_main_menu();
+
+        if let Err(err) = remove_saved_game() {
+            self.error_banner = Some(format!("Could not remove the saved game: {err}"));
+        }
+    }
+
+    pub fn quit_game(&mut self) {
+        self.quit_confirm_popup = false;
+
+        match self.save_unfinished_game() {
+            Ok(saved_game) => self.saved_game = saved_game.or(self.saved_game.take()),
+            Err(err) => self.error_banner = Some(format!("Could not save the game: {err}")),
+        }
+
+        self.refresh_main_menu();
+        self.return_to_menu();
+    }
+
+    /// Leaves the race too, the host counts the player as lost.
+    pub fn return_to_menu(&mut self) {
+        self.race = None;
+        self.current_screen = CurrentScreen::Menu;
+    }
+
+    /// Saves the current game if it is still being played, races can not be resumed.
+    pub fn save_unfinished_game(&self) -> io::Result<Option<SavedGame>> {
+        let is_unfinished = matches!(self.current_screen, CurrentScreen::Game)
+            && !self.game.info.is_game_over
+            && self.race.is_none();
+
+        if !is_unfinished {
+            return Ok(None);
+        }
+
+        let saved_game = SavedGame {
+            game: self.game.clone(),
+            saved_at: Local::now(),
+        };
+        save_game(&saved_game)?;
+
+        Ok(Some(saved_game))
+    }
+
+    pub fn on_tick(&mut self) {
+        self.poll_race();
+
+        match self.current_screen {
+            CurrentScreen::Game if self.game.check_time_limit() => self.lose_game(),
+            CurrentScreen::Replay => self.replay_advance_playback(),
+            _ => {}
+        }
+    }
+
+    fn lose_game(&mut self) {
+        self.guess_response = format!("YOU LOST! The number was {}", self.game.info.generated_number);
+        self.quit_confirm_popup = false;
+        self.mode = UserInputMode::GameOver;
+    }
+
     fn input_move_cursor_left(&mut self) {
         let cursor = self.user_input_info.character_index.saturating_sub(1);
         self.user_input_info.character_index = self.input_clamp_cursor(cursor);
@@ -101,6 +394,7 @@ impl App {
 
         self.user_input_info.input.insert(index, new_char);
         self.input_move_cursor_right();
+        self.guess_error = None;
     }
 
     pub fn input_delete_char(&mut self) {
@@ -130,63 +424,513 @@ impl App {
             return;
         }
 
-        self.user_input_history.push(GameMove {
-            user_value: self.user_input_info.input.clone().parse().unwrap(),
-            move_done_at: Local::now(),
-        });
-
-        self.user_input_history.sort_by_key(|x| Reverse(x.move_done_at));
-
-        self.user_input_info.input.clear();
+        let input = std::mem::take(&mut self.user_input_info.input);
         self.input_reset_cursor();
 
-        self.check_entered_guess();
+        self.check_entered_guess(&input);
     }
 
-    fn check_entered_guess(&mut self) {
-        let value = self.user_input_history.first().unwrap().user_value;
-        match value.cmp(&self.game_info.generated_number) {
-            Ordering::Less => {
-                self.game_info.current_guess_response = format!("Number is > than {value}");
+    fn check_entered_guess(&mut self, input: &str) {
+        self.guess_error = None;
+
+        if self.race.is_some() {
+            self.send_race_guess(input);
+            return;
+        }
+
+        let outcome = self.game.submit(input);
+        let value = self.game.history.last().map_or(0, |x| x.user_value);
+
+        match outcome {
+            GuessOutcome::TooLow => {
+                self.guess_response = format!("Number is > than {value}");
             },
-            Ordering::Greater => {
-                self.game_info.current_guess_response = format!("Number is < than {value}");
+            GuessOutcome::TooHigh => {
+                self.guess_response = format!("Number is < than {value}");
             },
-            Ordering::Equal => {
-                self.game_info.current_guess_response = "YOU WON !!!".to_owned();
-                self.game_info.is_game_over = true;
-                self.game_info.game_completed_at = Local::now();
+            GuessOutcome::Invalid(error) => {
+                self.guess_error = Some(error);
+            },
+            GuessOutcome::Correct if !self.game.info.players.is_empty() => {
+                let winner = self.game.winner().unwrap_or_default().to_string();
+                self.guess_response = format!("{winner} WON !!! Optimal strategy needs {} tries", self.game.optimal_tries());
+                self.mode = UserInputMode::GameOver;
+
+                // The winner may not be the player using this machine, so the active player stays.
+                let player = self.profiles.find_or_create(&winner);
+                self.save_profiles();
+                self.remap_scores_and_save(&player);
+            },
+            GuessOutcome::Correct => {
+                self.guess_response = format!("YOU WON !!! Optimal strategy needs {} tries", self.game.optimal_tries());
                 self.mode = UserInputMode::InputName;
+
+                if let Some(profile) = self.profiles.active() {
+                    self.user_input_info.input = profile.name.clone();
+                    self.user_input_info.character_index = profile.name.chars().count();
+                }
+            },
+            GuessOutcome::Lost => {
+                self.lose_game();
             },
         }
     }
 
-    pub fn input_submit_name(&mut self) {
-        if self.user_input_info.input.is_empty() {
+    pub fn show_hint(&mut self) {
+        if self.race.is_some() {
             return;
         }
 
-        self.user_name = self.user_input_info.input.clone();
+        let guess = self.game.use_hint();
+        let interval = self.game.feasible_interval();
+
+        self.guess_response = format!("Hint: try {guess}, the number is in {}-{}", interval.start(), interval.end());
+    }
+
+    pub fn input_submit_name(&mut self) {
+        let name = normalize_name(&self.user_input_info.input);
+
+        if name.is_empty() {
+            return;
+        }
 
         self.user_input_info.input.clear();
         self.input_reset_cursor();
 
-        self.remap_scores_and_save();
+        let player = self.profiles.select_or_create(&name);
+        self.save_profiles();
+        self.refresh_main_menu();
+
+        self.remap_scores_and_save(&player);
+        self.open_leaderboard();
+    }
+
+    fn remap_scores_and_save(&mut self, player: &Profile) {
+        match self.store.add_score(create_score(player, &self.game)) {
+            Ok(Some(backup_path)) => self.error_banner = Some(moved_aside_message(&backup_path)),
+            Ok(None) => {}
+            Err(err) => self.error_banner = Some(err.to_string()),
+        }
+    }
+
+    fn save_profiles(&mut self) {
+        if let Err(err) = self.profiles.save() {
+            self.error_banner = Some(format!("Could not save player profiles: {err}"));
+        }
+    }
+
+    pub fn open_profiles(&mut self) {
+        let selected = self.profiles.active_player_id.as_ref()
+            .and_then(|id| self.profiles.profiles.iter().position(|x| &x.id == id))
+            .or((!self.profiles.profiles.is_empty()).then_some(0));
+
+        self.profiles_info.list_state.select(selected);
+        self.profiles_info.edit = None;
+        self.profiles_info.error = None;
+        self.current_screen = CurrentScreen::Profiles;
+    }
+
+    pub fn profiles_move_selection(&mut self, offset: isize) {
+        let len = self.profiles.profiles.len();
+
+        if len == 0 {
+            return;
+        }
+
+        let selected = self.profiles_info.list_state.selected().unwrap_or(0);
+        self.profiles_info.list_state.select(Some(selected.saturating_add_signed(offset).min(len - 1)));
+    }
+
+    fn selected_profile_id(&self) -> Option<String> {
+        self.profiles_info.list_state.selected()
+            .and_then(|selected| self.profiles.profiles.get(selected))
+            .map(|x| x.id.clone())
+    }
+
+    pub fn profiles_select(&mut self) {
+        let Some(id) = self.selected_profile_id() else {
+            return;
+        };
+
+        self.profiles.select(&id);
+        self.save_profiles();
+        self.refresh_main_menu();
+        self.current_screen = CurrentScreen::Menu;
+    }
+
+    pub fn profiles_start_edit(&mut self, edit: ProfileEdit) {
+        let input = match edit {
+            ProfileEdit::New => String::new(),
+            ProfileEdit::Rename => match self.selected_profile_id().and_then(|id| self.profiles.find_by_id(&id)) {
+                Some(profile) => profile.name.clone(),
+                None => return,
+            },
+        };
+
+        self.profiles_info.edit = Some(edit);
+        self.profiles_info.input = input;
+        self.profiles_info.error = None;
+    }
+
+    pub fn profiles_cancel_edit(&mut self) {
+        self.profiles_info.edit = None;
+        self.profiles_info.error = None;
+    }
+
+    pub fn profiles_enter_char(&mut self, value: char) {
+        if self.profiles_info.input.chars().count() < PLAYER_NAME_MAX_LEN {
+            self.profiles_info.input.push(value);
+        }
+    }
+
+    pub fn profiles_delete_char(&mut self) {
+        self.profiles_info.input.pop();
+    }
+
+    pub fn profiles_submit_edit(&mut self) {
+        let name = normalize_name(&self.profiles_info.input);
+
+        let result = match self.profiles_info.edit {
+            Some(ProfileEdit::New) if name.is_empty() => Err("Name must not be empty".to_string()),
+            Some(ProfileEdit::New) if self.profiles.find_by_name(&name).is_some() =>
+                Err(format!("There is already a player called '{name}'")),
+            Some(ProfileEdit::New) => {
+                self.profiles.select_or_create(&name);
+                Ok(())
+            }
+            Some(ProfileEdit::Rename) => match self.selected_profile_id() {
+                Some(id) => self.profiles.rename(&id, &name),
+                None => Ok(()),
+            },
+            None => Ok(()),
+        };
+
+        match result {
+            Ok(()) => {
+                self.save_profiles();
+                self.refresh_main_menu();
+                self.open_profiles();
+            }
+            Err(error) => self.profiles_info.error = Some(error),
+        }
+    }
+
+    pub fn join_race(&mut self, address: &str, name: &str, host_port: Option<u16>) -> io::Result<()> {
+        self.race = Some(RaceInfo {
+            client: RaceClient::connect(address, name)?,
+            name: name.to_string(),
+            host_port,
+            players: Vec::new(),
+            expected_players: 0,
+            status: None,
+            is_waiting_for_answer: false,
+        });
+        self.current_screen = CurrentScreen::RaceLobby;
+
+        Ok(())
+    }
+
+    fn poll_race(&mut self) {
+        while let Some(race) = &mut self.race {
+            let message = match race.client.try_recv() {
+                Ok(message) => message,
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {
+                    race.status = Some("The connection to the host was lost".to_string());
+                    return;
+                }
+            };
+
+            match message {
+                HostMessage::Welcome { name } => race.name = name,
+                HostMessage::Lobby { players, expected_players } => {
+                    race.players = players.into_iter()
+                        .map(|name| RacePlayer { name, tries: 0, state: RacePlayerState::Playing })
+                        .collect();
+                    race.expected_players = expected_players;
+                }
+                HostMessage::Start { min_number, max_number } if validate_range(min_number, max_number).is_err() => {
+                    race.status = Some(format!("The host sent an invalid range {min_number}-{max_number}"));
+                }
+                HostMessage::Start { min_number, max_number } => {
+                    // The number of this game is a stand-in, the host answers the guesses.
+                    self.reset_game(Game::new(min_number..=max_number, GameRules::default(), &mut rand::thread_rng()));
+                    self.current_screen = CurrentScreen::Game;
+                }
+                HostMessage::Answer { value, answer } => {
+                    race.is_waiting_for_answer = false;
+                    self.game.record_answer(value, answer.outcome());
+
+                    self.guess_response = match answer {
+                        RaceAnswer::Higher => format!("Number is > than {value}"),
+                        RaceAnswer::Lower => format!("Number is < than {value}"),
+                        RaceAnswer::Correct => "Correct!".to_string(),
+                    };
+                }
+                HostMessage::Standings { players } => race.players = players,
+                HostMessage::Finished { winner, tries, number } => {
+                    self.game.info.generated_number = number;
+                    let tries = tries.unwrap_or_default();
+
+                    self.guess_response = match winner {
+                        Some(winner) if winner == race.name => format!("YOU WON THE RACE in {tries} tries !!!"),
+                        Some(winner) => format!("{winner} won the race in {tries} tries, the number was {number}"),
+                        None => format!("Nobody guessed it, the number was {number}"),
+                    };
+                    self.game.give_up();
+                    self.quit_confirm_popup = false;
+                    self.mode = UserInputMode::GameOver;
+                }
+                HostMessage::Error { message } => {
+                    race.is_waiting_for_answer = false;
+                    race.status = Some(message);
+                }
+            }
+        }
+    }
+
+    fn send_race_guess(&mut self, input: &str) {
+        let Some(race) = &mut self.race else {
+            return;
+        };
+
+        if race.is_waiting_for_answer || self.game.info.is_game_over {
+            return;
+        }
+
+        let value = match parse_guess(input).and_then(|value| self.game.validate_guess(value).map(|_| value)) {
+            Ok(value) => value,
+            Err(error) => {
+                self.guess_error = Some(error);
+                return;
+            }
+        };
+
+        match race.client.send(&ClientMessage::Guess { value }) {
+            Ok(()) => race.is_waiting_for_answer = true,
+            Err(err) => race.status = Some(format!("Could not reach the host: {err}")),
+        }
+    }
+
+    pub fn open_leaderboard(&mut self) {
         self.current_screen = CurrentScreen::Leaderboard;
+        self.reload_leaderboard();
+    }
+
+    fn reload_leaderboard(&mut self) {
+        self.leaderboard.table_state = TableState::default();
+        self.refresh_leaderboard();
+    }
+
+    pub fn refresh_leaderboard(&mut self) {
+        let query = ScoreQuery {
+            is_hard_mode: self.leaderboard.mode_filter.is_hard_mode(),
+            daily_challenge: self.leaderboard.is_daily_only.then(today),
+            ..ScoreQuery::default()
+        };
+
+        let mut scores = match self.store.load_scores(&query) {
+            Ok(scores) => scores,
+            Err(err) => {
+                self.error_banner = Some(err.to_string());
+                Vec::new()
+            }
+        };
+
+        self.profiles.apply_names(&mut scores);
+
+        let mut available_ranges: Vec<String> = scores.iter().map(|x| x.number_range.clone()).collect();
+        available_ranges.sort_by_key(|range| range_sort_key(range));
+        available_ranges.dedup();
+
+        self.leaderboard.available_ranges = available_ranges;
+        self.leaderboard.loaded_scores = scores;
+        self.show_leaderboard();
+    }
+
+    fn show_leaderboard(&mut self) {
+        let range_filter = &self.leaderboard.range_filter;
+        let mut scores: Vec<Score> = self.leaderboard.loaded_scores.iter()
+            .filter(|score| range_filter.as_ref().map_or(true, |range| &score.number_range == range))
+            .cloned()
+            .collect();
+
+        match self.leaderboard.sort_key {
+            LeaderboardSortKey::Points => scores.sort_by_key(|x| (Reverse(x.points), x.tries)),
+            LeaderboardSortKey::Tries => scores.sort_by_key(|x| (x.tries, x.completed_for_ms)),
+            LeaderboardSortKey::GameTime => scores.sort_by_key(|x| (x.completed_for_ms, x.tries)),
+            LeaderboardSortKey::Date => scores.sort_by_key(|x| Reverse(x.completed_at)),
+        }
+
+        let selected = match scores.len() {
+            0 => None,
+            len => Some(self.leaderboard.table_state.selected().unwrap_or(0).min(len - 1)),
+        };
+
+        self.leaderboard.table_state.select(selected);
+        self.leaderboard.scores = scores;
     }
 
-    fn remap_scores_and_save(&self) {
-        let tries = self.user_input_history.len() as i32;
-        let number_range = format!("{}-{}", self.game_info.min_number, self.game_info.max_number);
-        let time_diff = self.game_info.game_completed_at.time() - self.game_info.game_started_at.time();
-        let ms_diff = time_diff.num_milliseconds();
+    pub fn leaderboard_cycle_range(&mut self) {
+        let ranges = &self.leaderboard.available_ranges;
+        let next_index = match &self.leaderboard.range_filter {
+            None => 0,
+            Some(range) => ranges.iter().position(|x| x == range).map_or(ranges.len(), |i| i + 1),
+        };
 
-        add_score(self.user_name.clone(),
-          tries, number_range,
-          self.game_info.game_started_at,
-          self.game_info.game_completed_at,
-          ms_diff,
-          self.game_info.is_hard_mode
-        );
+        self.leaderboard.range_filter = ranges.get(next_index).cloned();
+        self.leaderboard.table_state = TableState::default();
+        self.show_leaderboard();
     }
-}
\ No newline at end of file
+
+    pub fn leaderboard_cycle_mode(&mut self) {
+        self.leaderboard.mode_filter = self.leaderboard.mode_filter.next();
+        self.reload_leaderboard();
+    }
+
+    pub fn leaderboard_toggle_daily(&mut self) {
+        self.leaderboard.is_daily_only = !self.leaderboard.is_daily_only;
+        self.reload_leaderboard();
+    }
+
+    pub fn leaderboard_cycle_sort(&mut self) {
+        self.leaderboard.sort_key = self.leaderboard.sort_key.next();
+        self.leaderboard.table_state = TableState::default();
+        self.show_leaderboard();
+    }
+
+    pub fn open_replay(&mut self) {
+        let Some(score) = self.leaderboard.table_state.selected()
+            .and_then(|selected| self.leaderboard.scores.get(selected)) else {
+            return;
+        };
+
+        self.replay = Some(ReplayInfo {
+            score: score.clone(),
+            step: 0,
+            is_playing: false,
+            playback_started_at: Instant::now(),
+            playback_started_from_ms: 0,
+        });
+        self.current_screen = CurrentScreen::Replay;
+    }
+
+    pub fn replay_move_step(&mut self, offset: isize) {
+        if let Some(replay) = &mut self.replay {
+            replay.is_playing = false;
+            replay.step = replay.step.saturating_add_signed(offset).min(replay.score.moves.len());
+        }
+    }
+
+    pub fn replay_toggle_playback(&mut self) {
+        let Some(replay) = &mut self.replay else {
+            return;
+        };
+
+        if replay.is_playing {
+            replay.is_playing = false;
+            return;
+        }
+
+        if replay.step >= replay.score.moves.len() {
+            replay.step = 0;
+        }
+
+        replay.is_playing = true;
+        replay.playback_started_at = Instant::now();
+        replay.playback_started_from_ms = match replay.step {
+            0 => 0,
+            step => (replay.score.moves[step - 1].move_done_at - replay.score.started_at).num_milliseconds(),
+        };
+    }
+
+    // Shows every move that had been made by the same time in the original game.
+    fn replay_advance_playback(&mut self) {
+        let Some(replay) = self.replay.as_mut().filter(|x| x.is_playing) else {
+            return;
+        };
+
+        let position_ms = replay.playback_started_from_ms + replay.playback_started_at.elapsed().as_millis() as i64;
+
+        replay.step = replay.score.moves.iter()
+            .filter(|x| (x.move_done_at - replay.score.started_at).num_milliseconds() <= position_ms)
+            .count();
+
+        if replay.step >= replay.score.moves.len() {
+            replay.is_playing = false;
+        }
+    }
+
+    /// Writes the scores as they are shown right now to a file in the current directory.
+    pub fn leaderboard_export(&mut self, format: ExportFormat) {
+        self.leaderboard.is_export_popup = false;
+
+        let path = format!("leaderboard-{}.{}", Local::now().format("%Y%m%d-%H%M%S"), format.extension());
+        let result = export_scores(&self.leaderboard.scores, format)
+            .map_err(io::Error::from)
+            .and_then(|export| fs::write(&path, export));
+
+        match result {
+            Ok(()) => self.leaderboard.status = Some(format!("Exported {} scores to {path}", self.leaderboard.scores.len())),
+            Err(err) => self.error_banner = Some(format!("Export failed: {err}")),
+        }
+    }
+
+    pub fn leaderboard_import(&mut self) {
+        let Some(path) = self.leaderboard.import_path_input.take() else {
+            return;
+        };
+
+        let result = read_score_file(Path::new(path.trim())).and_then(|mut scores| {
+            self.profiles.adopt_scores(&mut scores);
+            self.store.import_scores(scores)
+        });
+
+        match result {
+            Ok(report) => {
+                self.leaderboard.status = Some(match report.conflicts.first() {
+                    Some(conflict) => format!("{}. Conflict: {conflict}", report.summary()),
+                    None => report.summary(),
+                });
+                self.refresh_leaderboard();
+            }
+            Err(err) => self.error_banner = Some(err.to_string()),
+        }
+    }
+
+    pub fn leaderboard_move_selection(&mut self, offset: isize) {
+        let len = self.leaderboard.scores.len();
+
+        if len == 0 {
+            return;
+        }
+
+        let selected = self.leaderboard.table_state.selected().unwrap_or(0);
+        let selected = selected.saturating_add_signed(offset).min(len - 1);
+        self.leaderboard.table_state.select(Some(selected));
+    }
+}
+
+fn parse_range_bound(field_name: &str, input: &str) -> Result<i32, String> {
+    if input.is_empty() {
+        return Err(format!("{field_name} is required"));
+    }
+
+    match input.parse::<i32>() {
+        Ok(value) if value < 0 => Err(format!("{field_name} must not be negative")),
+        Ok(value) => Ok(value),
+        Err(_) => Err(format!("{field_name} must be between 0 and {}", i32::MAX)),
+    }
+}
+
+fn parse_rule_limit(field_name: &str, input: &str) -> Result<Option<u32>, String> {
+    if input.is_empty() {
+        return Ok(None);
+    }
+
+    match input.parse::<u32>() {
+        Ok(0) => Err(format!("{field_name} must be greater than 0")),
+        Ok(value) => Ok(Some(value)),
+        Err(_) => Err(format!("{field_name} must be at most {}", u32::MAX)),
+    }
+}
@@ -0,0 +1,126 @@
+use std::path::PathBuf;
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand};
+use crate::export::ExportFormat;
+use crate::race::DEFAULT_RACE_PORT;
+use crate::scores::StoreKind;
+
+#[derive(Parser)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Seed for the secret number, the same seed always gives the same number
+    #[arg(long, global = true)]
+    pub seed: Option<u64>,
+
+    /// Leaderboard file to use instead of the one in the data directory
+    #[arg(long, global = true, env = "RUST_GUESS_SCORES")]
+    pub scores_file: Option<PathBuf>,
+
+    /// Leaderboard storage
+    #[arg(long, global = true, value_enum, env = "RUST_GUESS_STORE", default_value_t = StoreKind::Json)]
+    pub store: StoreKind,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Start a game right away
+    Play {
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(i32).range(0..))]
+        min: i32,
+        #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(i32).range(0..))]
+        max: i32,
+        /// Hide the guesses history
+        #[arg(long)]
+        hard: bool,
+        /// Maximum number of guesses before the game is lost
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        max_guesses: Option<u32>,
+        /// Lose the game after this many seconds
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        time_limit: Option<u32>,
+        /// Invalid guesses (out of range, repeated or contradicting the hints) use up a guess
+        #[arg(long)]
+        count_invalid: bool,
+        /// Read guesses line by line from stdin instead of opening the terminal UI
+        #[arg(long)]
+        plain: bool,
+    },
+    /// Host a race on this machine, it starts once all the players have joined
+    Host {
+        #[arg(long, default_value_t = DEFAULT_RACE_PORT)]
+        port: u16,
+        /// Number of players in the race, including you
+        #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(2..))]
+        players: u8,
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(i32).range(0..))]
+        min: i32,
+        #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(i32).range(0..))]
+        max: i32,
+        /// Your name in the race, the active player by default
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Join a race hosted on another machine
+    Join {
+        /// Address of the host, e.g. 192.168.1.20 or 192.168.1.20:7878
+        address: String,
+        /// Your name in the race, the active player by default
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Print the leaderboard
+    Leaderboard {
+        /// Show only games played in this range, e.g. 1-100
+        #[arg(long)]
+        range: Option<String>,
+        /// Show only hard mode games
+        #[arg(long)]
+        hard: bool,
+        /// Show only games of this player
+        #[arg(long)]
+        player: Option<String>,
+        /// Show only games completed on this date, e.g. 2024-06-01
+        #[arg(long)]
+        date: Option<NaiveDate>,
+        /// Show at most this many scores
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Skip this many of the best scores, e.g. to show the second page
+        #[arg(long, default_value_t = 0)]
+        offset: usize,
+        /// Print scores as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print statistics of a single player
+    Stats {
+        #[arg(long)]
+        name: String,
+    },
+    /// Export the leaderboard as CSV, Markdown, JSON or an HTML report
+    Export {
+        #[arg(long, value_enum)]
+        format: ExportFormat,
+        /// File to write, the export is printed when omitted
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// Export only games played in this range, e.g. 1-100
+        #[arg(long)]
+        range: Option<String>,
+        /// Export only hard mode games
+        #[arg(long)]
+        hard: bool,
+        /// Export only games of this player
+        #[arg(long)]
+        player: Option<String>,
+    },
+    /// Merge a leaderboard file from another machine into this one
+    Import {
+        file: PathBuf,
+    },
+    /// Remove all scores from the leaderboard
+    ResetScores,
+}
@@ -0,0 +1,131 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::export::{export_scores, ExportFormat};
+use crate::formatting::format_duration_ms;
+use crate::profiles::PlayerProfiles;
+use crate::scores::{read_score_file, ScoreQuery, ScoreStore, ScoreStoreError};
+
+pub fn print_leaderboard(store: &dyn ScoreStore, profiles: &PlayerProfiles, query: &ScoreQuery,
+                         as_json: bool) -> Result<(), ScoreStoreError> {
+    let mut scores = store.load_scores(query)?;
+    profiles.apply_names(&mut scores);
+
+    if as_json {
+        let json = serde_json::to_string_pretty(&scores).map_err(ScoreStoreError::Serialize)?;
+        println!("{json}");
+        return Ok(());
+    }
+
+    if scores.is_empty() {
+        println!("No scores yet");
+        return Ok(());
+    }
+
+    let name_len = scores.iter().map(|x| x.name.chars().count()).max().unwrap_or(0).max(4);
+    let range_len = scores.iter().map(|x| x.number_range.len()).max().unwrap_or(0).max(10);
+
+    println!("{:>3}  {:<name_len$}  {:>6}  {:>5}  {:<range_len$}  {:<4}  {:>10}",
+             "#", "Name", "Points", "Tries", "Game range", "Mode", "Game time");
+
+    for (i, score) in scores.iter().enumerate() {
+        println!("{:>3}  {:<name_len$}  {:>6}  {:>5}  {:<range_len$}  {:<4}  {:>10}",
+                 query.offset + i + 1,
+                 score.name,
+                 score.points,
+                 score.tries,
+                 score.number_range,
+                 score.mode_label(),
+                 format_duration_ms(score.completed_for_ms));
+    }
+
+    Ok(())
+}
+
+pub fn print_stats(store: &dyn ScoreStore, profiles: &PlayerProfiles, name: &str) -> Result<(), ScoreStoreError> {
+    let profile = profiles.find_by_name(name);
+    let scores = store.load_scores(&ScoreQuery {
+        player: Some(name.to_string()),
+        player_id: profile.map(|x| x.id.clone()),
+        ..ScoreQuery::default()
+    })?;
+    let name = profile.map_or(name, |x| x.name.as_str());
+
+    if scores.is_empty() {
+        println!("No games found for '{name}'");
+        return Ok(());
+    }
+
+    let games_won = scores.len();
+    let total_tries: i64 = scores.iter().map(|x| x.tries as i64).sum();
+    let best_tries = scores.iter().map(|x| x.tries).min().unwrap_or(0);
+    let best_points = scores.iter().map(|x| x.points).max().unwrap_or(0);
+    let total_points: u64 = scores.iter().map(|x| x.points as u64).sum();
+    let fastest_ms = scores.iter().map(|x| x.completed_for_ms).min().unwrap_or(0);
+    let fastest_first_guess_ms = scores.iter().filter_map(|x| x.first_guess_ms).min();
+    let slowest_guess_ms = scores.iter().filter_map(|x| x.slowest_guess_ms).max();
+    let hard_games = scores.iter().filter(|x| x.is_hard_mode).count();
+    let daily_games = scores.iter().filter(|x| x.daily_challenge.is_some()).count();
+
+    let mut ranges: Vec<&str> = scores.iter().map(|x| x.number_range.as_str()).collect();
+    ranges.sort();
+    ranges.dedup();
+
+    println!("Player:          {name}");
+    println!("Games won:       {games_won}");
+    println!("Hard mode games: {hard_games}");
+    println!("Daily games:     {daily_games}");
+    println!("Total points:    {total_points}");
+    println!("Best points:     {best_points}");
+    println!("Best tries:      {best_tries}");
+    println!("Average tries:   {:.1}", total_tries as f64 / games_won as f64);
+    println!("Fastest game:    {}", format_duration_ms(fastest_ms));
+
+    if let Some(first_guess_ms) = fastest_first_guess_ms {
+        println!("Fastest start:   {}", format_duration_ms(first_guess_ms));
+    }
+    if let Some(slowest_guess_ms) = slowest_guess_ms {
+        println!("Slowest guess:   {}", format_duration_ms(slowest_guess_ms));
+    }
+    println!("Ranges played:   {}", ranges.join(", "));
+
+    Ok(())
+}
+
+pub fn export_leaderboard(store: &dyn ScoreStore, profiles: &PlayerProfiles, query: &ScoreQuery,
+                          format: ExportFormat, output: Option<PathBuf>) -> Result<(), ScoreStoreError> {
+    let mut scores = store.load_scores(query)?;
+    profiles.apply_names(&mut scores);
+
+    let export = export_scores(&scores, format).map_err(ScoreStoreError::Serialize)?;
+
+    match output {
+        Some(path) => {
+            fs::write(&path, export)?;
+            eprintln!("Exported {} scores to {}", scores.len(), path.display());
+        }
+        None => print!("{export}"),
+    }
+
+    Ok(())
+}
+
+pub fn import_leaderboard(store: &dyn ScoreStore, profiles: &PlayerProfiles, path: &Path) -> Result<(), ScoreStoreError> {
+    let mut scores = read_score_file(path)?;
+    profiles.adopt_scores(&mut scores);
+
+    let report = store.import_scores(scores)?;
+
+    for conflict in &report.conflicts {
+        println!("Conflict: {conflict}");
+    }
+    println!("{}", report.summary());
+
+    Ok(())
+}
+
+pub fn reset_scores(store: &dyn ScoreStore) -> Result<(), ScoreStoreError> {
+    store.clear_scores()?;
+    println!("Leaderboard cleared");
+
+    Ok(())
+}
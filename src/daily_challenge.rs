@@ -0,0 +1,12 @@
+use chrono::{Datelike, Local, NaiveDate};
+
+pub const DAILY_CHALLENGE_MIN_NUMBER: i32 = 1;
+pub const DAILY_CHALLENGE_MAX_NUMBER: i32 = 1000;
+
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+pub fn daily_challenge_seed(date: NaiveDate) -> u64 {
+    (date.year() as u64) * 10_000 + (date.month() as u64) * 100 + date.day() as u64
+}
@@ -0,0 +1,209 @@
+use std::fmt::Write;
+use chrono::Local;
+use clap::ValueEnum;
+use crate::formatting::format_duration_ms;
+use crate::models::score::Score;
+use crate::scoring::range_sort_key;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    Markdown,
+    Json,
+    Html,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Json => "json",
+            ExportFormat::Html => "html",
+        }
+    }
+}
+
+const COLUMNS: [&str; 9] = ["#", "Name", "Points", "Tries", "Game range", "Mode", "Game time", "Completed at", "Daily"];
+
+// Spreadsheets run cells starting with one of these as formulas, a leading `'` keeps them text.
+const FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+/// Renders scores in the given order, ranks are positions in `scores`.
+pub fn export_scores(scores: &[Score], format: ExportFormat) -> serde_json::Result<String> {
+    match format {
+        ExportFormat::Csv => Ok(export_csv(scores)),
+        ExportFormat::Markdown => Ok(export_markdown(scores)),
+        ExportFormat::Json => serde_json::to_string_pretty(scores),
+        ExportFormat::Html => Ok(export_html(scores)),
+    }
+}
+
+fn score_row(rank: usize, score: &Score) -> [String; 9] {
+    [
+        rank.to_string(),
+        score.name.clone(),
+        score.points.to_string(),
+        score.tries.to_string(),
+        score.number_range.clone(),
+        if score.is_hard_mode { "hard" } else { "normal" }.to_string(),
+        format_duration_ms(score.completed_for_ms),
+        score.completed_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        score.daily_challenge.map(|x| x.to_string()).unwrap_or_default(),
+    ]
+}
+
+fn export_csv(scores: &[Score]) -> String {
+    let mut csv = String::new();
+    let _ = writeln!(csv, "{}", COLUMNS.map(escape_csv).join(","));
+
+    for (i, score) in scores.iter().enumerate() {
+        let _ = writeln!(csv, "{}", score_row(i + 1, score).map(|x| escape_csv(&x)).join(","));
+    }
+
+    csv
+}
+
+fn escape_csv(value: &str) -> String {
+    let value = if value.starts_with(FORMULA_PREFIXES) { format!("'{value}") } else { value.to_string() };
+
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn export_markdown(scores: &[Score]) -> String {
+    let mut markdown = String::new();
+    let _ = writeln!(markdown, "| {} |", COLUMNS.join(" | "));
+    let _ = writeln!(markdown, "|{}", ["---|"; COLUMNS.len()].concat());
+
+    for (i, score) in scores.iter().enumerate() {
+        let row = score_row(i + 1, score).map(|x| x.replace('|', "\\|"));
+        let _ = writeln!(markdown, "| {} |", row.join(" | "));
+    }
+
+    markdown
+}
+
+// Scores are grouped by range, so every section is ranked on its own.
+fn export_html(scores: &[Score]) -> String {
+    let mut ranges: Vec<&str> = scores.iter().map(|x| x.number_range.as_str()).collect();
+    ranges.sort_by_key(|range| range_sort_key(range));
+    ranges.dedup();
+
+    let mut html = String::new();
+    let _ = write!(html, "{}", HTML_HEAD);
+    let _ = writeln!(html, "<h1>Leaderboard</h1>");
+    let _ = writeln!(html, "<p class=\"generated\">Generated {}</p>", Local::now().format("%Y-%m-%d %H:%M"));
+
+    if ranges.is_empty() {
+        let _ = writeln!(html, "<p>No scores yet</p>");
+    }
+
+    for range in ranges {
+        let _ = writeln!(html, "<section>\n<h2>{}</h2>\n<table>", escape_html(range));
+        let _ = writeln!(html, "<tr>{}</tr>", COLUMNS.map(|x| format!("<th>{}</th>", escape_html(x))).concat());
+
+        for (i, score) in scores.iter().filter(|x| x.number_range == range).enumerate() {
+            let row = score_row(i + 1, score).map(|x| format!("<td>{}</td>", escape_html(&x)));
+            let _ = writeln!(html, "<tr>{}</tr>", row.concat());
+        }
+
+        let _ = writeln!(html, "</table>\n</section>");
+    }
+
+    let _ = writeln!(html, "</body>\n</html>");
+    html
+}
+
+fn escape_html(value: &str) -> String {
+    value.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+const HTML_HEAD: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Rust Guess leaderboard</title>
+<style>
+body { font-family: system-ui, sans-serif; margin: 2rem auto; max-width: 60rem; color: #1e293b; }
+h1 { margin-bottom: 0; }
+.generated { color: #64748b; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 2rem; }
+th { background: #1e3a8a; color: #e2e8f0; text-align: left; }
+th, td { padding: 0.3rem 0.6rem; }
+tr:nth-child(even) td { background: #f1f5f9; }
+</style>
+</head>
+<body>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use super::*;
+
+    fn score(name: &str) -> Score {
+        let completed_at = Local.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+
+        Score {
+            name: name.to_string(),
+            player_id: None,
+            tries: 7,
+            started_at: completed_at - chrono::Duration::milliseconds(83_400),
+            completed_at,
+            completed_for_ms: 83_400,
+            number_range: "1-100".to_string(),
+            is_hard_mode: false,
+            seed: None,
+            daily_challenge: None,
+            points: 700,
+            first_guess_ms: None,
+            slowest_guess_ms: None,
+            secret_number: None,
+            moves: Vec::new(),
+            hints_used: 0,
+            hot_seat_players: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn csv_cells_are_quoted_and_kept_from_running_as_formulas() {
+        let cases = [
+            ("alice", "alice"),
+            ("Smith, Jo", "\"Smith, Jo\""),
+            ("the \"ace\"", "\"the \"\"ace\"\"\""),
+            ("=1+1", "'=1+1"),
+            ("+49 123", "'+49 123"),
+            ("-bob", "'-bob"),
+            ("@SUM(A1)", "'@SUM(A1)"),
+            ("=HYPERLINK(\"x\",\"y\")", "\"'=HYPERLINK(\"\"x\"\",\"\"y\"\")\""),
+        ];
+
+        for (name, expected) in cases {
+            let csv = export_scores(&[score(name)], ExportFormat::Csv).unwrap();
+            let row = csv.lines().nth(1).unwrap();
+
+            assert_eq!(row, format!("1,{expected},700,7,1-100,normal,1m 23.4s,2024-06-01 12:00:00,"), "{name}");
+        }
+    }
+
+    #[test]
+    fn markdown_and_html_cells_are_escaped() {
+        let scores = [score("a|b <script>&'\"")];
+
+        let markdown = export_scores(&scores, ExportFormat::Markdown).unwrap();
+        assert_eq!(markdown.lines().nth(2).unwrap(),
+                   "| 1 | a\\|b <script>&'\" | 700 | 7 | 1-100 | normal | 1m 23.4s | 2024-06-01 12:00:00 |  |");
+
+        let html = export_scores(&scores, ExportFormat::Html).unwrap();
+        assert!(html.contains("<td>a|b &lt;script&gt;&amp;&#39;&quot;</td>"));
+        assert!(!html.contains("<script>"));
+    }
+}
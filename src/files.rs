@@ -0,0 +1,62 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, Write};
+use std::path::{Path, PathBuf};
+use chrono::Local;
+use serde::de::DeserializeOwned;
+
+/// Writes to a temporary file next to `path` first, so readers never see a half written file.
+pub fn write_file_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent().filter(|x| !x.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(format!(".tmp-{}", std::process::id()));
+    let temp_path = PathBuf::from(temp_path);
+
+    let result = File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp_path, path));
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+
+    result
+}
+
+/// Moves an unreadable file to `<path>.corrupt-<timestamp>`, so it is kept but no longer read.
+pub fn move_aside_corrupted(path: &Path) -> io::Result<PathBuf> {
+    let mut backup_path = path.as_os_str().to_owned();
+    backup_path.push(format!(".corrupt-{}", Local::now().format("%Y%m%d%H%M%S")));
+    let backup_path = PathBuf::from(backup_path);
+
+    fs::rename(path, &backup_path)?;
+    Ok(backup_path)
+}
+
+/// Returns `None` if the file does not exist yet.
+pub fn read_json_file<T: DeserializeOwned>(path: &Path) -> io::Result<Option<T>> {
+    let file = match File::open(path) {
+        Ok(f) => f,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+
+    Ok(Some(serde_json::from_reader(BufReader::new(file))?))
+}
+
+/// Like `read_json_file`, but an unreadable file is moved aside, so it does not fail every launch.
+/// The error then says where the file was moved.
+pub fn read_json_file_or_move_aside<T: DeserializeOwned>(path: &Path) -> io::Result<Option<T>> {
+    match read_json_file(path) {
+        Err(err) if matches!(err.kind(), io::ErrorKind::InvalidData | io::ErrorKind::UnexpectedEof) => {
+            let backup_path = move_aside_corrupted(path)?;
+            Err(io::Error::new(err.kind(), format!("{err}, it was moved to {}", backup_path.display())))
+        }
+        result => result,
+    }
+}
@@ -0,0 +1,42 @@
+const MS_IN_SECOND: u64 = 1000;
+const MS_IN_MINUTE: u64 = 60 * MS_IN_SECOND;
+const MS_IN_HOUR: u64 = 60 * MS_IN_MINUTE;
+
+/// Formats a duration as `1h 2m 3.4s`, leading zero parts are left out.
+pub fn format_duration_ms(ms: i64) -> String {
+    let sign = if ms < 0 { "-" } else { "" };
+    let ms = ms.unsigned_abs();
+
+    let hours = ms / MS_IN_HOUR;
+    let minutes = ms % MS_IN_HOUR / MS_IN_MINUTE;
+    let tenths = ms % MS_IN_MINUTE / 100;
+    let seconds = format!("{}.{}s", tenths / 10, tenths % 10);
+
+    match (hours, minutes) {
+        (0, 0) => format!("{sign}{seconds}"),
+        (0, _) => format!("{sign}{minutes}m {seconds}"),
+        _ => format!("{sign}{hours}h {minutes}m {seconds}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_leave_out_leading_zero_parts() {
+        let cases = [
+            (0, "0.0s"),
+            (950, "0.9s"),
+            (12_345, "12.3s"),
+            (83_400, "1m 23.4s"),
+            (3_600_000, "1h 0m 0.0s"),
+            (3_723_400, "1h 2m 3.4s"),
+            (-83_400, "-1m 23.4s"),
+        ];
+
+        for (ms, expected) in cases {
+            assert_eq!(format_duration_ms(ms), expected, "{ms} ms");
+        }
+    }
+}
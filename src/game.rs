@@ -0,0 +1,525 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::num::IntErrorKind;
+use std::ops::RangeInclusive;
+use chrono::{DateTime, Duration, Local};
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use crate::models::game_info::GameInfo;
+use crate::models::game_move::GameMove;
+use crate::models::hot_seat_player::HotSeatPlayer;
+use crate::models::game_rules::GameRules;
+use crate::solver::{feasible_interval, optimal_guess, optimal_tries_for};
+
+#[derive(Clone, Copy)]
+pub enum GuessOutcome {
+    TooLow,
+    TooHigh,
+    Correct,
+    Invalid(GuessError),
+    Lost,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GuessError {
+    NotANumber,
+    Overflow,
+    OutOfRange { min_number: i32, max_number: i32 },
+    Duplicate(i32),
+    ContradictsHint { low: i32, high: i32 },
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuessError::NotANumber => write!(f, "Not a number"),
+            GuessError::Overflow => write!(f, "The number is too big"),
+            GuessError::OutOfRange { min_number, max_number } =>
+                write!(f, "The number must be in {min_number}-{max_number}"),
+            GuessError::Duplicate(value) => write!(f, "{value} was already guessed"),
+            GuessError::ContradictsHint { low, high } =>
+                write!(f, "The hints say the number is in {low}-{high}"),
+        }
+    }
+}
+
+impl std::error::Error for GuessError {}
+
+pub fn parse_guess(input: &str) -> Result<i32, GuessError> {
+    input.trim().parse().map_err(|err: std::num::ParseIntError| match err.kind() {
+        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => GuessError::Overflow,
+        _ => GuessError::NotANumber,
+    })
+}
+
+pub fn validate_range(min_number: i32, max_number: i32) -> Result<(), String> {
+    if min_number < 0 || max_number < 0 {
+        return Err("Numbers must not be negative".to_string());
+    }
+
+    if min_number >= max_number {
+        return Err("Min number must be less than max number".to_string());
+    }
+
+    Ok(())
+}
+
+pub fn compare_guess(value: i32, secret_number: i32) -> GuessOutcome {
+    match value.cmp(&secret_number) {
+        Ordering::Less => GuessOutcome::TooLow,
+        Ordering::Greater => GuessOutcome::TooHigh,
+        Ordering::Equal => GuessOutcome::Correct,
+    }
+}
+
+// `gen_range` is not guaranteed to pick the same number in other rand versions, this is.
+fn pick_number<R: RngCore>(range: &RangeInclusive<i32>, rng: &mut R) -> i32 {
+    let span = (*range.end() as i64 - *range.start() as i64 + 1) as u64;
+
+    (*range.start() as i64 + (rng.next_u64() % span) as i64) as i32
+}
+
+pub fn move_durations(started_at: DateTime<Local>, moves: &[GameMove]) -> Vec<Duration> {
+    let mut previous_at = started_at;
+
+    moves.iter().map(|game_move| {
+        let duration = game_move.move_done_at - previous_at;
+        previous_at = game_move.move_done_at;
+        duration
+    }).collect()
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Game {
+    pub info: GameInfo,
+    pub history: Vec<GameMove>,
+}
+
+impl Game {
+    pub fn new<R: Rng>(range: RangeInclusive<i32>, rules: GameRules, rng: &mut R) -> Game {
+        let now = Local::now();
+
+        Game {
+            info: GameInfo {
+                min_number: *range.start(),
+                max_number: *range.end(),
+                generated_number: pick_number(&range, rng),
+                is_game_over: false,
+                is_game_lost: false,
+                game_started_at: now,
+                game_completed_at: now,
+                rules,
+                seed: None,
+                daily_challenge: None,
+                hints_used: 0,
+                invalid_guesses: 0,
+                players: Vec::new(),
+            },
+            history: Vec::new(),
+        }
+    }
+
+    /// ChaCha8 gives the same numbers on every platform and rand version, so races and daily
+    /// challenges played on different builds get the same secret number.
+    pub fn from_seed(range: RangeInclusive<i32>, rules: GameRules, seed: u64) -> Game {
+        let mut game = Game::new(range, rules, &mut ChaCha8Rng::seed_from_u64(seed));
+        game.info.seed = Some(seed);
+        game
+    }
+
+    pub fn from_optional_seed(range: RangeInclusive<i32>, rules: GameRules, seed: Option<u64>) -> Game {
+        Game::from_seed(range, rules, seed.unwrap_or_else(|| rand::thread_rng().gen()))
+    }
+
+    /// Parses and makes a guess typed by the player.
+    pub fn submit(&mut self, input: &str) -> GuessOutcome {
+        match parse_guess(input) {
+            Ok(value) => self.guess(value),
+            Err(error) => self.finished_outcome().unwrap_or_else(|| self.reject_guess(error)),
+        }
+    }
+
+    pub fn guess(&mut self, value: i32) -> GuessOutcome {
+        if let Some(outcome) = self.finished_outcome() {
+            return outcome;
+        }
+
+        if let Err(error) = self.validate_guess(value) {
+            return self.reject_guess(error);
+        }
+
+        self.history.push(GameMove {
+            user_value: value,
+            move_done_at: Local::now(),
+        });
+
+        match compare_guess(value, self.info.generated_number) {
+            GuessOutcome::Correct => {
+                self.finish(false);
+                GuessOutcome::Correct
+            }
+            _ if self.remaining_guesses() == Some(0) => {
+                self.finish(true);
+                GuessOutcome::Lost
+            }
+            outcome => outcome,
+        }
+    }
+
+    /// Records a race guess answered by the host. The player does not know the number, so the
+    /// game keeps one the answers so far allow, which gives the same hints as the real one.
+    pub fn record_answer(&mut self, value: i32, outcome: GuessOutcome) {
+        if self.info.is_game_over {
+            return;
+        }
+
+        // Earlier guesses are all outside the interval, so they keep their hints.
+        self.info.generated_number = match outcome {
+            GuessOutcome::TooLow => value + 1,
+            GuessOutcome::TooHigh => *self.feasible_interval().start(),
+            _ => value,
+        };
+
+        self.history.push(GameMove {
+            user_value: value,
+            move_done_at: Local::now(),
+        });
+
+        if matches!(outcome, GuessOutcome::Correct) {
+            self.finish(false);
+        }
+    }
+
+    pub fn validate_guess(&self, value: i32) -> Result<(), GuessError> {
+        let (min_number, max_number) = (self.info.min_number, self.info.max_number);
+
+        if value < min_number || value > max_number {
+            return Err(GuessError::OutOfRange { min_number, max_number });
+        }
+
+        if self.history.iter().any(|x| x.user_value == value) {
+            return Err(GuessError::Duplicate(value));
+        }
+
+        let interval = self.feasible_interval();
+
+        if !interval.contains(&value) {
+            return Err(GuessError::ContradictsHint { low: *interval.start(), high: *interval.end() });
+        }
+
+        Ok(())
+    }
+
+    /// Ends the game as lost once the time limit is exceeded, returns `true` if it happened now.
+    pub fn check_time_limit(&mut self) -> bool {
+        if self.info.is_game_over {
+            return false;
+        }
+
+        let is_time_up = self.remaining_time().is_some_and(|time| time <= Duration::zero());
+
+        if is_time_up {
+            self.finish(true);
+        }
+
+        is_time_up
+    }
+
+    /// Ends the game as lost, e.g. when another player of a race guessed the number first.
+    pub fn give_up(&mut self) {
+        if !self.info.is_game_over {
+            self.finish(true);
+        }
+    }
+
+    pub fn tries(&self) -> usize {
+        self.history.len() + self.info.invalid_guesses as usize
+    }
+
+    /// Hot-seat players take turns in order, only valid guesses pass the turn.
+    pub fn player_of_move(&self, index: usize) -> Option<&str> {
+        match self.info.players.len() {
+            0 => None,
+            len => Some(self.info.players[index % len].as_str()),
+        }
+    }
+
+    pub fn current_player(&self) -> Option<&str> {
+        self.player_of_move(self.history.len())
+    }
+
+    pub fn winner(&self) -> Option<&str> {
+        if !self.info.is_game_over || self.info.is_game_lost {
+            return None;
+        }
+
+        self.player_of_move(self.history.len().checked_sub(1)?)
+    }
+
+    pub fn player_tries(&self) -> Vec<HotSeatPlayer> {
+        let len = self.info.players.len();
+
+        self.info.players.iter().enumerate().map(|(i, name)| HotSeatPlayer {
+            name: name.clone(),
+            tries: self.history.len().saturating_sub(i).div_ceil(len) as u32,
+        }).collect()
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        let finished_at = if self.info.is_game_over {
+            self.info.game_completed_at
+        } else {
+            Local::now()
+        };
+
+        finished_at - self.info.game_started_at
+    }
+
+    /// Time each guess took, counted from the previous guess or from the start of the game.
+    pub fn move_durations(&self) -> Vec<Duration> {
+        move_durations(self.info.game_started_at, &self.history)
+    }
+
+    pub fn hints(&self) -> Vec<(i32, GuessOutcome)> {
+        self.history.iter()
+            .map(|x| (x.user_value, compare_guess(x.user_value, self.info.generated_number)))
+            .collect()
+    }
+
+    pub fn feasible_interval(&self) -> RangeInclusive<i32> {
+        feasible_interval(self.info.min_number..=self.info.max_number, &self.hints())
+    }
+
+    /// Reveals the optimal next guess, every hint lowers the points of the game.
+    pub fn use_hint(&mut self) -> i32 {
+        self.info.hints_used += 1;
+        optimal_guess(&self.feasible_interval())
+    }
+
+    pub fn optimal_tries(&self) -> u32 {
+        optimal_tries_for(self.info.min_number..=self.info.max_number, self.info.generated_number)
+    }
+
+    pub fn remaining_guesses(&self) -> Option<u32> {
+        self.info.rules.max_guesses
+            .map(|max_guesses| max_guesses.saturating_sub(self.tries() as u32))
+    }
+
+    pub fn remaining_time(&self) -> Option<Duration> {
+        self.info.rules.time_limit_secs.map(|time_limit| {
+            Duration::seconds(time_limit as i64) - self.elapsed()
+        })
+    }
+
+    /// Moves all the game times forward, so the time the game was suspended is not counted.
+    pub fn resume_after(&mut self, suspended_for: Duration) {
+        self.info.game_started_at += suspended_for;
+        self.info.game_completed_at += suspended_for;
+
+        for game_move in self.history.iter_mut() {
+            game_move.move_done_at += suspended_for;
+        }
+    }
+
+    fn finished_outcome(&mut self) -> Option<GuessOutcome> {
+        if self.info.is_game_over {
+            return Some(if self.info.is_game_lost { GuessOutcome::Lost } else { GuessOutcome::Correct });
+        }
+
+        self.check_time_limit().then_some(GuessOutcome::Lost)
+    }
+
+    fn reject_guess(&mut self, error: GuessError) -> GuessOutcome {
+        if !self.info.rules.count_invalid_guesses {
+            return GuessOutcome::Invalid(error);
+        }
+
+        self.info.invalid_guesses += 1;
+
+        if self.remaining_guesses() == Some(0) {
+            self.finish(true);
+            return GuessOutcome::Lost;
+        }
+
+        GuessOutcome::Invalid(error)
+    }
+
+    fn finish(&mut self, is_lost: bool) {
+        self.info.is_game_over = true;
+        self.info.is_game_lost = is_lost;
+        self.info.game_completed_at = Local::now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED: u64 = 42;
+
+    // The seed picks the number, tests pin it so the guesses below read naturally.
+    fn game_with_secret(rules: GameRules, secret_number: i32) -> Game {
+        let mut game = Game::from_seed(1..=100, rules, SEED);
+        game.info.generated_number = secret_number;
+        game
+    }
+
+    #[test]
+    fn same_seed_gives_same_number() {
+        let first = Game::from_seed(1..=1000, GameRules::default(), SEED);
+        let second = Game::from_seed(1..=1000, GameRules::default(), SEED);
+
+        assert_eq!(first.info.generated_number, second.info.generated_number);
+        assert_eq!(first.info.seed, Some(SEED));
+        assert!((1..=1000).contains(&first.info.generated_number));
+    }
+
+    #[test]
+    fn seeded_numbers_do_not_change_between_builds() {
+        let numbers: Vec<i32> = [0, 1, SEED, 20240601]
+            .map(|seed| Game::from_seed(1..=1000, GameRules::default(), seed).info.generated_number)
+            .to_vec();
+
+        assert_eq!(numbers, vec![653, 202, 738, 572]);
+    }
+
+    #[test]
+    fn guesses_are_answered_until_the_number_is_found() {
+        let mut game = game_with_secret(GameRules::default(), 60);
+
+        assert!(matches!(game.guess(50), GuessOutcome::TooLow));
+        assert!(matches!(game.guess(70), GuessOutcome::TooHigh));
+        assert!(matches!(game.submit(" 60 "), GuessOutcome::Correct));
+
+        assert_eq!(game.tries(), 3);
+        assert!(game.info.is_game_over);
+        assert!(!game.info.is_game_lost);
+        // A finished game keeps answering with its result and records nothing.
+        assert!(matches!(game.guess(10), GuessOutcome::Correct));
+        assert_eq!(game.history.len(), 3);
+    }
+
+    #[test]
+    fn answers_of_the_race_host_give_the_same_hints() {
+        let mut game = game_with_secret(GameRules::default(), 1);
+
+        game.record_answer(50, GuessOutcome::TooHigh);
+        game.record_answer(20, GuessOutcome::TooLow);
+        game.record_answer(35, GuessOutcome::TooHigh);
+
+        let hints: Vec<bool> = game.hints().iter().map(|(_, x)| matches!(x, GuessOutcome::TooHigh)).collect();
+        assert_eq!(hints, vec![true, false, true]);
+        assert_eq!(game.feasible_interval(), 21..=34);
+        assert!(!game.info.is_game_over);
+
+        game.record_answer(30, GuessOutcome::Correct);
+        assert_eq!(game.info.generated_number, 30);
+        assert_eq!(game.tries(), 4);
+        assert!(game.info.is_game_over && !game.info.is_game_lost);
+    }
+
+    #[test]
+    fn game_is_lost_when_guesses_run_out() {
+        let rules = GameRules { max_guesses: Some(2), ..GameRules::default() };
+        let mut game = game_with_secret(rules, 60);
+
+        assert_eq!(game.remaining_guesses(), Some(2));
+        assert!(matches!(game.guess(50), GuessOutcome::TooLow));
+        assert_eq!(game.remaining_guesses(), Some(1));
+        assert!(matches!(game.guess(55), GuessOutcome::Lost));
+
+        assert_eq!(game.remaining_guesses(), Some(0));
+        assert!(game.info.is_game_lost);
+        assert!(matches!(game.guess(60), GuessOutcome::Lost));
+    }
+
+    #[test]
+    fn last_guess_wins_when_it_is_correct() {
+        let rules = GameRules { max_guesses: Some(1), ..GameRules::default() };
+        let mut game = game_with_secret(rules, 60);
+
+        assert!(matches!(game.guess(60), GuessOutcome::Correct));
+        assert!(!game.info.is_game_lost);
+    }
+
+    #[test]
+    fn game_is_lost_when_time_runs_out() {
+        let rules = GameRules { time_limit_secs: Some(10), ..GameRules::default() };
+        let mut game = game_with_secret(rules, 60);
+
+        assert!(!game.check_time_limit());
+        assert!(game.remaining_time().is_some_and(|x| x > Duration::zero()));
+
+        game.info.game_started_at -= Duration::seconds(11);
+
+        assert!(game.check_time_limit());
+        assert!(game.info.is_game_lost);
+        // Only the first check ends the game.
+        assert!(!game.check_time_limit());
+        assert!(matches!(game.guess(60), GuessOutcome::Lost));
+    }
+
+    #[test]
+    fn input_that_is_not_a_valid_number_is_rejected() {
+        assert_eq!(parse_guess(" 42\n"), Ok(42));
+        assert_eq!(parse_guess("forty"), Err(GuessError::NotANumber));
+        assert_eq!(parse_guess(""), Err(GuessError::NotANumber));
+        assert_eq!(parse_guess("99999999999"), Err(GuessError::Overflow));
+        assert_eq!(parse_guess("-99999999999"), Err(GuessError::Overflow));
+    }
+
+    #[test]
+    fn invalid_guesses_are_rejected_without_using_a_try() {
+        let mut game = game_with_secret(GameRules::default(), 60);
+        game.guess(50);
+        game.guess(70);
+
+        let errors = [
+            game.guess(0),
+            game.guess(101),
+            game.guess(50),
+            game.guess(45),
+            game.guess(75),
+            game.submit("abc"),
+        ].map(|outcome| match outcome {
+            GuessOutcome::Invalid(error) => Some(error),
+            _ => None,
+        });
+
+        assert_eq!(errors, [
+            Some(GuessError::OutOfRange { min_number: 1, max_number: 100 }),
+            Some(GuessError::OutOfRange { min_number: 1, max_number: 100 }),
+            Some(GuessError::Duplicate(50)),
+            Some(GuessError::ContradictsHint { low: 51, high: 69 }),
+            Some(GuessError::ContradictsHint { low: 51, high: 69 }),
+            Some(GuessError::NotANumber),
+        ]);
+        assert_eq!(game.tries(), 2);
+        assert_eq!(game.info.invalid_guesses, 0);
+    }
+
+    #[test]
+    fn counted_invalid_guesses_use_up_tries_and_can_lose_the_game() {
+        let rules = GameRules { max_guesses: Some(3), count_invalid_guesses: true, ..GameRules::default() };
+        let mut game = game_with_secret(rules, 60);
+
+        assert!(matches!(game.guess(50), GuessOutcome::TooLow));
+        assert!(matches!(game.guess(50), GuessOutcome::Invalid(GuessError::Duplicate(50))));
+        assert_eq!(game.tries(), 2);
+        assert_eq!(game.remaining_guesses(), Some(1));
+
+        assert!(matches!(game.submit("x"), GuessOutcome::Lost));
+        assert!(game.info.is_game_lost);
+        assert_eq!(game.info.invalid_guesses, 2);
+        assert_eq!(game.history.len(), 1);
+    }
+
+    #[test]
+    fn unlimited_game_has_no_remaining_guesses_or_time() {
+        let game = game_with_secret(GameRules::default(), 60);
+
+        assert_eq!(game.remaining_guesses(), None);
+        assert!(game.remaining_time().is_none());
+    }
+}
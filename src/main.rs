@@ -1,64 +1,255 @@
-use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use ratatui::{
     backend::{Backend, CrosstermBackend},
     Terminal,
 };
+use clap::Parser;
 use std::io::{self, Result};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 use crate::app::App;
+use crate::cli::{Cli, Command};
+use crate::commands::{export_leaderboard, import_leaderboard, print_leaderboard, print_stats, reset_scores};
+use crate::export::ExportFormat;
+use crate::game::{validate_range, Game};
+use crate::plain::run_plain_game;
+use crate::profiles::{load_profiles, normalize_name, PlayerProfiles};
+use crate::race::host::{start_host, RaceSettings};
+use crate::race::DEFAULT_RACE_PORT;
+use crate::scores::{open_store, ScoreQuery, ScoreStoreError};
+use crate::terminal::{register_exit_signals, TerminalGuard};
 use crate::models::current_screen::CurrentScreen;
+use crate::models::custom_game_field::CustomGameField;
+use crate::models::game_rules::GameRules;
+use crate::models::menu_item::MenuItem;
+use crate::models::profile_edit::ProfileEdit;
 use crate::models::user_input_mode::UserInputMode;
 use crate::ui_builder::ui_builder;
 
 mod app;
+mod cli;
+mod commands;
+mod daily_challenge;
+mod export;
+mod files;
+mod formatting;
+mod game;
 mod models;
+mod plain;
+mod profiles;
+mod race;
+mod saves;
 mod ui_builder;
 mod scores;
+mod scoring;
+mod solver;
+mod terminal;
 mod ui;
 
+const TICK_RATE_MS: u64 = 250;
+const LEADERBOARD_PAGE_SIZE: isize = 15;
+
 fn main() -> Result<()> {
-    enable_raw_mode()?;
+    let cli = Cli::parse();
 
-    let mut stderr = io::stderr();
-    execute!(stderr, EnterAlternateScreen, EnableMouseCapture)?;
+    let store = match open_store(cli.store, cli.scores_file) {
+        Ok(store) => store,
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+    };
 
-    let backend = CrosstermBackend::new(stderr);
-    let mut terminal = Terminal::new(backend)?;
-    let res = run_app(&mut terminal);
+    let (profiles, profiles_error) = match load_profiles() {
+        Ok(profiles) => (profiles, None),
+        Err(err) => (PlayerProfiles::default(), Some(format!("Could not load player profiles: {err}"))),
+    };
+
+    if let Some(message) = &profiles_error {
+        eprintln!("{message}");
+    }
+
+    match cli.command {
+        Some(Command::Play { min, max, hard, max_guesses, time_limit, count_invalid, plain }) => {
+            if let Err(err) = validate_range(min, max) {
+                eprintln!("{err}");
+                std::process::exit(2);
+            }
+
+            let rules = GameRules {
+                is_hard_mode: hard,
+                max_guesses,
+                time_limit_secs: time_limit,
+                count_invalid_guesses: count_invalid,
+            };
+
+            if plain {
+                return run_plain_game(Game::from_optional_seed(min..=max, rules, cli.seed), store.as_ref(), profiles);
+            }
+
+            let mut app = App::new(cli.seed, store, profiles, profiles_error);
+            app.current_screen = CurrentScreen::Game;
+            app.start_game(min, max, rules);
+
+            run_tui(app)
+        }
+        Some(Command::Host { port, players, min, max, name }) => {
+            if let Err(err) = validate_range(min, max) {
+                eprintln!("{err}");
+                std::process::exit(2);
+            }
+
+            let name = race_player_name(name, &profiles);
+            let settings = RaceSettings {
+                min_number: min,
+                max_number: max,
+                seed: cli.seed.unwrap_or_else(rand::random),
+                expected_players: players as usize,
+            };
+
+            let address = match start_host(port, settings) {
+                Ok(address) => address,
+                Err(err) => {
+                    eprintln!("Could not listen on port {port}: {err}");
+                    std::process::exit(1);
+                }
+            };
+
+            let mut app = App::new(cli.seed, store, profiles, profiles_error);
+            exit_on_race_error(app.join_race(&format!("127.0.0.1:{}", address.port()), &name, Some(address.port())));
+
+            run_tui(app)
+        }
+        Some(Command::Join { address, name }) => {
+            let name = race_player_name(name, &profiles);
+            let address = if address.contains(':') { address } else { format!("{address}:{DEFAULT_RACE_PORT}") };
 
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture,
-    )?;
+            let mut app = App::new(cli.seed, store, profiles, profiles_error);
+            exit_on_race_error(app.join_race(&address, &name, None));
 
-    terminal.show_cursor()?;
+            run_tui(app)
+        }
+        Some(Command::Leaderboard { range, hard, player, date, limit, offset, json }) => {
+            let query = ScoreQuery {
+                number_range: range,
+                is_hard_mode: hard.then_some(true),
+                player_id: player.as_ref().and_then(|x| profiles.find_by_name(x)).map(|x| x.id.clone()),
+                player,
+                completed_on: date,
+                offset,
+                limit,
+                ..ScoreQuery::default()
+            };
+
+            exit_on_store_error(print_leaderboard(store.as_ref(), &profiles, &query, json))
+        }
+        Some(Command::Stats { name }) => {
+            exit_on_store_error(print_stats(store.as_ref(), &profiles, &name))
+        }
+        Some(Command::Export { format, output, range, hard, player }) => {
+            let query = ScoreQuery {
+                number_range: range,
+                is_hard_mode: hard.then_some(true),
+                player_id: player.as_ref().and_then(|x| profiles.find_by_name(x)).map(|x| x.id.clone()),
+                player,
+                ..ScoreQuery::default()
+            };
+
+            exit_on_store_error(export_leaderboard(store.as_ref(), &profiles, &query, format, output))
+        }
+        Some(Command::Import { file }) => {
+            exit_on_store_error(import_leaderboard(store.as_ref(), &profiles, &file))
+        }
+        Some(Command::ResetScores) => {
+            exit_on_store_error(reset_scores(store.as_ref()))
+        }
+        None => run_tui(App::new(cli.seed, store, profiles, profiles_error)),
+    }
+}
 
-    if let Ok(_) = res {
-    } else if let Err(err) = res {
-        println!("{err:?}");
+fn exit_on_store_error(result: std::result::Result<(), ScoreStoreError>) -> Result<()> {
+    if let Err(err) = result {
+        eprintln!("{err}");
+        std::process::exit(1);
     }
 
     Ok(())
 }
 
-fn run_app<B: Backend>(terminal: &mut Terminal<B>) -> Result<bool> {
-    let mut app = App::new();
+fn race_player_name(name: Option<String>, profiles: &PlayerProfiles) -> String {
+    let name = name.map(|x| normalize_name(&x))
+        .or_else(|| profiles.active().map(|x| x.name.clone()))
+        .unwrap_or_default();
+
+    if name.is_empty() {
+        eprintln!("Choose a player in the menu or pass --name");
+        std::process::exit(2);
+    }
+
+    name
+}
+
+fn exit_on_race_error(result: io::Result<()>) {
+    if let Err(err) = result {
+        eprintln!("Could not join the race: {err}");
+        std::process::exit(1);
+    }
+}
+
+fn run_tui(mut app: App) -> Result<()> {
+    let exit_requested = register_exit_signals()?;
+    let guard = TerminalGuard::enter()?;
+
+    let backend = CrosstermBackend::new(io::stderr());
+    let mut terminal = Terminal::new(backend)?;
+    let res = panic::catch_unwind(AssertUnwindSafe(|| run_app(&mut terminal, &mut app, &exit_requested)));
 
+    drop(guard);
+
+    // Anything but quitting from the menu leaves the current game unfinished.
+    if !matches!(res, Ok(Ok(_))) || exit_requested.load(Ordering::Relaxed) {
+        match app.save_unfinished_game() {
+            Ok(Some(_)) => eprintln!("The game in progress was saved"),
+            Ok(None) => {}
+            Err(err) => eprintln!("Could not save the game in progress: {err}"),
+        }
+    }
+
+    match res {
+        Ok(Err(err)) => println!("{err:?}"),
+        Ok(Ok(_)) => {}
+        Err(panic) => panic::resume_unwind(panic),
+    }
+
+    Ok(())
+}
+
+fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App, exit_requested: &AtomicBool) -> Result<bool> {
     loop {
-        terminal.draw(|f| ui_builder(f, &mut app))?;
+        if exit_requested.load(Ordering::Relaxed) {
+            return Ok(false);
+        }
+
+        terminal.draw(|f| ui_builder(f, app))?;
+
+        if !event::poll(Duration::from_millis(TICK_RATE_MS))? {
+            app.on_tick();
+            continue;
+        }
 
         if let Event::Key(key) = event::read()? {
             if key.kind == KeyEventKind::Release {
                 continue;
             }
 
+            app.error_banner = None;
+
             match app.current_screen {
                 CurrentScreen::Game => match key.code {
+                    KeyCode::Char('q') | KeyCode::Enter if matches!(app.mode, UserInputMode::GameOver) => {
+                        app.return_to_menu();
+                    }
                     KeyCode::Char('q') => match app.mode {
                         UserInputMode::InputName => continue,
                         _ => {
@@ -67,16 +258,14 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>) -> Result<bool> {
                             }
                         }
                     },
-                    KeyCode::Char('y') => {
-                        if app.quit_confirm_popup {
-                            app.current_screen = CurrentScreen::Menu;
-                            app.quit_confirm_popup = false;
-                        }
+                    KeyCode::Char('y') if app.quit_confirm_popup => {
+                        app.quit_game();
                     }
-                    KeyCode::Char('n') => {
-                        if app.quit_confirm_popup {
-                            app.quit_confirm_popup = false;
-                        }
+                    KeyCode::Char('n') if app.quit_confirm_popup => {
+                        app.quit_confirm_popup = false;
+                    }
+                    KeyCode::Char('h') if matches!(app.mode, UserInputMode::InputNumber) && !app.quit_confirm_popup => {
+                        app.show_hint();
                     }
                     KeyCode::Char(value) => match app.mode {
                         UserInputMode::InputNumber => {
@@ -89,11 +278,13 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>) -> Result<bool> {
                                 app.input_enter_char(value);
                             }
                         }
+                        UserInputMode::GameOver => {}
                     },
                     KeyCode::Backspace => match app.mode {
                         UserInputMode::InputNumber | UserInputMode::InputName => {
                             app.input_delete_char();
                         }
+                        UserInputMode::GameOver => {}
                     },
                     KeyCode::Enter => match app.mode {
                         UserInputMode::InputNumber => {
@@ -102,59 +293,233 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>) -> Result<bool> {
                         UserInputMode::InputName => {
                             app.input_submit_name();
                         }
+                        UserInputMode::GameOver => {}
                     },
 
                     _ => {}
                 },
+                CurrentScreen::Leaderboard if app.leaderboard.import_path_input.is_some() => match key.code {
+                    KeyCode::Esc => {
+                        app.leaderboard.import_path_input = None;
+                    }
+                    KeyCode::Char(value) => {
+                        app.leaderboard.import_path_input.get_or_insert_with(String::new).push(value);
+                    }
+                    KeyCode::Backspace => {
+                        app.leaderboard.import_path_input.get_or_insert_with(String::new).pop();
+                    }
+                    KeyCode::Enter => {
+                        app.leaderboard_import();
+                    }
+                    _ => {}
+                },
+                CurrentScreen::Leaderboard if app.leaderboard.is_export_popup => match key.code {
+                    KeyCode::Char('c') => {
+                        app.leaderboard_export(ExportFormat::Csv);
+                    }
+                    KeyCode::Char('m') => {
+                        app.leaderboard_export(ExportFormat::Markdown);
+                    }
+                    KeyCode::Char('j') => {
+                        app.leaderboard_export(ExportFormat::Json);
+                    }
+                    KeyCode::Char('h') => {
+                        app.leaderboard_export(ExportFormat::Html);
+                    }
+                    KeyCode::Esc | KeyCode::Char('q') => {
+                        app.leaderboard.is_export_popup = false;
+                    }
+                    _ => {}
+                },
                 CurrentScreen::Leaderboard => match key.code {
+                    KeyCode::Char('e') => {
+                        app.leaderboard.status = None;
+                        app.leaderboard.is_export_popup = true;
+                    }
+                    KeyCode::Char('i') => {
+                        app.leaderboard.status = None;
+                        app.leaderboard.import_path_input = Some(String::new());
+                    }
                     KeyCode::Char('q') => {
                         app.current_screen = CurrentScreen::Menu;
                     }
+                    KeyCode::Char('d') => {
+                        app.leaderboard_toggle_daily();
+                    }
+                    KeyCode::Char('r') => {
+                        app.leaderboard_cycle_range();
+                    }
+                    KeyCode::Enter => {
+                        app.open_replay();
+                    }
+                    KeyCode::Char('m') => {
+                        app.leaderboard_cycle_mode();
+                    }
+                    KeyCode::Char('s') => {
+                        app.leaderboard_cycle_sort();
+                    }
+                    KeyCode::Up => {
+                        app.leaderboard_move_selection(-1);
+                    }
+                    KeyCode::Down => {
+                        app.leaderboard_move_selection(1);
+                    }
+                    KeyCode::PageUp => {
+                        app.leaderboard_move_selection(-LEADERBOARD_PAGE_SIZE);
+                    }
+                    KeyCode::PageDown => {
+                        app.leaderboard_move_selection(LEADERBOARD_PAGE_SIZE);
+                    }
+                    KeyCode::Home => {
+                        app.leaderboard_move_selection(isize::MIN);
+                    }
+                    KeyCode::End => {
+                        app.leaderboard_move_selection(isize::MAX);
+                    }
                     _ => {}
                 },
-                CurrentScreen::Menu => match key.code {
+                CurrentScreen::Replay => match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => {
+                        app.current_screen = CurrentScreen::Leaderboard;
+                    }
+                    KeyCode::Left => {
+                        app.replay_move_step(-1);
+                    }
+                    KeyCode::Right => {
+                        app.replay_move_step(1);
+                    }
+                    KeyCode::Home => {
+                        app.replay_move_step(isize::MIN);
+                    }
+                    KeyCode::End => {
+                        app.replay_move_step(isize::MAX);
+                    }
+                    KeyCode::Char(' ') => {
+                        app.replay_toggle_playback();
+                    }
+                    _ => {}
+                },
+                CurrentScreen::Profiles if app.profiles_info.edit.is_some() => match key.code {
+                    KeyCode::Esc => {
+                        app.profiles_cancel_edit();
+                    }
+                    KeyCode::Char(value) => {
+                        app.profiles_enter_char(value);
+                    }
+                    KeyCode::Backspace => {
+                        app.profiles_delete_char();
+                    }
+                    KeyCode::Enter => {
+                        app.profiles_submit_edit();
+                    }
+                    _ => {}
+                },
+                CurrentScreen::Profiles => match key.code {
+                    KeyCode::Esc | KeyCode::Char('q') => {
+                        app.current_screen = CurrentScreen::Menu;
+                    }
                     KeyCode::Up => {
-                        if app.get_selected_menu_idx() > 0 {
-                            let index = app.get_selected_menu_idx();
-                            app.main_menu_item_selected.select(Some(index - 1));
-                        }
+                        app.profiles_move_selection(-1);
                     }
                     KeyCode::Down => {
-                        if app.get_selected_menu_idx() < app.main_menu_items.len() - 1 {
-                            let index = app.get_selected_menu_idx();
-                            app.main_menu_item_selected.select(Some(index + 1));
-                        }
+                        app.profiles_move_selection(1);
+                    }
+                    KeyCode::Char('n') => {
+                        app.profiles_start_edit(ProfileEdit::New);
+                    }
+                    KeyCode::Char('r') => {
+                        app.profiles_start_edit(ProfileEdit::Rename);
+                    }
+                    KeyCode::Enter => {
+                        app.profiles_select();
+                    }
+                    _ => {}
+                },
+                CurrentScreen::HotSeatSetup => match key.code {
+                    KeyCode::Esc => {
+                        app.current_screen = CurrentScreen::Menu;
+                    }
+                    KeyCode::Char(value) => {
+                        app.hot_seat_enter_char(value);
+                    }
+                    KeyCode::Backspace => {
+                        app.hot_seat_delete_char();
+                    }
+                    KeyCode::Enter => {
+                        app.hot_seat_submit();
+                    }
+                    _ => {}
+                },
+                CurrentScreen::RaceLobby => match key.code {
+                    KeyCode::Esc | KeyCode::Char('q') => {
+                        app.return_to_menu();
+                    }
+                    _ => {}
+                },
+                CurrentScreen::CustomGame => match key.code {
+                    KeyCode::Esc | KeyCode::Char('q') => {
+                        app.current_screen = CurrentScreen::Menu;
+                    }
+                    KeyCode::Up | KeyCode::BackTab => {
+                        app.custom_game_info.selected_field = app.custom_game_info.selected_field.previous();
+                    }
+                    KeyCode::Down | KeyCode::Tab => {
+                        app.custom_game_info.selected_field = app.custom_game_info.selected_field.next();
+                    }
+                    KeyCode::Char(' ') | KeyCode::Enter
+                        if app.custom_game_info.selected_field == CustomGameField::HardMode => {
+                        app.custom_game_info.is_hard_mode = !app.custom_game_info.is_hard_mode;
+                    }
+                    KeyCode::Char(' ') | KeyCode::Enter
+                        if app.custom_game_info.selected_field == CustomGameField::CountInvalid => {
+                        app.custom_game_info.is_invalid_guess_counted = !app.custom_game_info.is_invalid_guess_counted;
+                    }
+                    KeyCode::Char(value) => {
+                        app.custom_game_enter_char(value);
+                    }
+                    KeyCode::Backspace => {
+                        app.custom_game_delete_char();
                     }
                     KeyCode::Enter => {
+                        app.custom_game_submit();
+                    }
+                    _ => {}
+                },
+                CurrentScreen::Menu => match key.code {
+                    KeyCode::Up if app.get_selected_menu_idx() > 0 => {
                         let index = app.get_selected_menu_idx();
-
-                        match index {
-                            0 => { //1-100 easy
-                                app.current_screen = CurrentScreen::Game;
-                                app.start_game(1, 100, false);
-                            }
-                            1 => { //1-1000 hard
-                                app.current_screen = CurrentScreen::Game;
-                                app.start_game(1, 100, true);
-                            }
-                            2 => { //1-1000 easy
-                                app.current_screen = CurrentScreen::Game;
-                                app.start_game(1, 1000, false);
-                            }
-                            3 => { //1-1000 hard
-                                app.current_screen = CurrentScreen::Game;
-                                app.start_game(1, 1000, true);
-                            }
-                            4 => { //1-1000000 hard
-                                app.current_screen = CurrentScreen::Game;
-                                app.start_game(1, 1000000, true);
-                            }
-                            5 => {
-                                app.current_screen = CurrentScreen::Leaderboard;
-                            }
-                            _ => return Ok(false),
-                        }
+                        app.main_menu_item_selected.select(Some(index - 1));
                     }
+                    KeyCode::Down if app.get_selected_menu_idx() < app.main_menu_items.len() - 1 => {
+                        let index = app.get_selected_menu_idx();
+                        app.main_menu_item_selected.select(Some(index + 1));
+                    }
+                    KeyCode::Enter => match app.get_selected_menu_item() {
+                        MenuItem::ResumeGame { .. } => {
+                            app.resume_game();
+                        }
+                        MenuItem::QuickGame => {
+                            app.current_screen = CurrentScreen::Game;
+                            app.start_game(1, 100, GameRules::default());
+                        }
+                        MenuItem::CustomGame => {
+                            app.open_custom_game();
+                        }
+                        MenuItem::DailyChallenge => {
+                            app.current_screen = CurrentScreen::Game;
+                            app.start_daily_challenge();
+                        }
+                        MenuItem::HotSeat => {
+                            app.open_hot_seat_setup();
+                        }
+                        MenuItem::Players { .. } => {
+                            app.open_profiles();
+                        }
+                        MenuItem::Leaderboard => {
+                            app.open_leaderboard();
+                        }
+                        MenuItem::Quit => return Ok(false),
+                    },
                     _ => {}
                 },
             }
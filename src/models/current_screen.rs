@@ -1,5 +1,10 @@
 pub enum CurrentScreen {
     Game,
     Menu,
+    CustomGame,
     Leaderboard,
-}
\ No newline at end of file
+    Replay,
+    Profiles,
+    RaceLobby,
+    HotSeatSetup,
+}
@@ -0,0 +1,36 @@
+#[derive(Clone, Copy, PartialEq)]
+pub enum CustomGameField {
+    MinNumber,
+    MaxNumber,
+    MaxGuesses,
+    TimeLimit,
+    HardMode,
+    CountInvalid,
+    Start,
+}
+
+impl CustomGameField {
+    pub fn next(&self) -> CustomGameField {
+        match self {
+            CustomGameField::MinNumber => CustomGameField::MaxNumber,
+            CustomGameField::MaxNumber => CustomGameField::MaxGuesses,
+            CustomGameField::MaxGuesses => CustomGameField::TimeLimit,
+            CustomGameField::TimeLimit => CustomGameField::HardMode,
+            CustomGameField::HardMode => CustomGameField::CountInvalid,
+            CustomGameField::CountInvalid => CustomGameField::Start,
+            CustomGameField::Start => CustomGameField::MinNumber,
+        }
+    }
+
+    pub fn previous(&self) -> CustomGameField {
+        match self {
+            CustomGameField::MinNumber => CustomGameField::Start,
+            CustomGameField::MaxNumber => CustomGameField::MinNumber,
+            CustomGameField::MaxGuesses => CustomGameField::MaxNumber,
+            CustomGameField::TimeLimit => CustomGameField::MaxGuesses,
+            CustomGameField::HardMode => CustomGameField::TimeLimit,
+            CustomGameField::CountInvalid => CustomGameField::HardMode,
+            CustomGameField::Start => CustomGameField::CountInvalid,
+        }
+    }
+}
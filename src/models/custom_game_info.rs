@@ -0,0 +1,12 @@
+use crate::models::custom_game_field::CustomGameField;
+
+pub struct CustomGameInfo {
+    pub min_input: String,
+    pub max_input: String,
+    pub max_guesses_input: String,
+    pub time_limit_input: String,
+    pub is_hard_mode: bool,
+    pub is_invalid_guess_counted: bool,
+    pub selected_field: CustomGameField,
+    pub error: Option<String>,
+}
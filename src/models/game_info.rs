@@ -1,14 +1,24 @@
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, NaiveDate};
 use serde::{Deserialize, Serialize};
+use crate::models::game_rules::GameRules;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GameInfo {
     pub min_number: i32,
     pub max_number: i32,
     pub generated_number: i32,
-    pub current_guess_response: String,
     pub is_game_over: bool,
+    pub is_game_lost: bool,
     pub game_started_at: DateTime<Local>,
     pub game_completed_at: DateTime<Local>,
-    pub is_hard_mode: bool,
-}
\ No newline at end of file
+    pub rules: GameRules,
+    pub seed: Option<u64>,
+    pub daily_challenge: Option<NaiveDate>,
+    #[serde(default)]
+    pub hints_used: u32,
+    #[serde(default)]
+    pub invalid_guesses: u32,
+    /// Players of a hot-seat game in turn order, empty for a single player.
+    #[serde(default)]
+    pub players: Vec<String>,
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct GameRules {
+    pub is_hard_mode: bool,
+    pub max_guesses: Option<u32>,
+    pub time_limit_secs: Option<u32>,
+    #[serde(default)]
+    pub count_invalid_guesses: bool,
+}
@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HotSeatPlayer {
+    pub name: String,
+    pub tries: u32,
+}
@@ -0,0 +1,5 @@
+pub struct HotSeatSetupInfo {
+    pub players: Vec<String>,
+    pub input: String,
+    pub error: Option<String>,
+}
@@ -0,0 +1,13 @@
+#[derive(Default)]
+pub struct ImportReport {
+    pub added: usize,
+    pub duplicates: usize,
+    pub conflicts: Vec<String>,
+}
+
+impl ImportReport {
+    pub fn summary(&self) -> String {
+        format!("Imported {} scores, skipped {} duplicates, {} conflicts",
+                self.added, self.duplicates, self.conflicts.len())
+    }
+}
@@ -0,0 +1,20 @@
+use ratatui::widgets::TableState;
+use crate::models::leaderboard_sort_key::LeaderboardSortKey;
+use crate::models::mode_filter::ModeFilter;
+use crate::models::score::Score;
+
+pub struct LeaderboardInfo {
+    /// Scores of the mode and daily filters as loaded from the store, the range filter and
+    /// the sort only change which of them `scores` shows.
+    pub loaded_scores: Vec<Score>,
+    pub scores: Vec<Score>,
+    pub available_ranges: Vec<String>,
+    pub range_filter: Option<String>,
+    pub mode_filter: ModeFilter,
+    pub is_daily_only: bool,
+    pub sort_key: LeaderboardSortKey,
+    pub table_state: TableState,
+    pub is_export_popup: bool,
+    pub import_path_input: Option<String>,
+    pub status: Option<String>,
+}
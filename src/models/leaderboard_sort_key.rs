@@ -0,0 +1,27 @@
+#[derive(Clone, Copy, PartialEq)]
+pub enum LeaderboardSortKey {
+    Points,
+    Tries,
+    GameTime,
+    Date,
+}
+
+impl LeaderboardSortKey {
+    pub fn next(&self) -> LeaderboardSortKey {
+        match self {
+            LeaderboardSortKey::Points => LeaderboardSortKey::Tries,
+            LeaderboardSortKey::Tries => LeaderboardSortKey::GameTime,
+            LeaderboardSortKey::GameTime => LeaderboardSortKey::Date,
+            LeaderboardSortKey::Date => LeaderboardSortKey::Points,
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        match self {
+            LeaderboardSortKey::Points => "points",
+            LeaderboardSortKey::Tries => "tries",
+            LeaderboardSortKey::GameTime => "time",
+            LeaderboardSortKey::Date => "date",
+        }
+    }
+}
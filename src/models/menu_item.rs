@@ -0,0 +1,32 @@
+use crate::daily_challenge::today;
+
+pub enum MenuItem {
+    ResumeGame { min_number: i32, max_number: i32, tries: usize },
+    QuickGame,
+    CustomGame,
+    DailyChallenge,
+    HotSeat,
+    Players { active_name: Option<String> },
+    Leaderboard,
+    Quit,
+}
+
+impl MenuItem {
+    pub fn title(&self) -> String {
+        match self {
+            MenuItem::ResumeGame { min_number, max_number, tries } => {
+                format!("Resume game ({min_number}-{max_number}, {tries} {})", if *tries == 1 { "guess" } else { "guesses" })
+            }
+            MenuItem::QuickGame => "Start game (1-100)".to_string(),
+            MenuItem::CustomGame => "Custom game".to_string(),
+            MenuItem::DailyChallenge => format!("Daily challenge ({})", today()),
+            MenuItem::HotSeat => "Hot-seat game (2-8 players)".to_string(),
+            MenuItem::Players { active_name } => match active_name {
+                Some(name) => format!("Player: {name}"),
+                None => "Choose player".to_string(),
+            },
+            MenuItem::Leaderboard => "Leaderboard".to_string(),
+            MenuItem::Quit => "Quit".to_string(),
+        }
+    }
+}
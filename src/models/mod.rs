@@ -3,4 +3,23 @@ pub mod current_screen;
 pub mod game_move;
 pub mod game_info;
 pub mod user_input_mode;
-pub mod user_input_info;
\ No newline at end of file
+pub mod user_input_info;
+pub mod menu_item;
+pub mod custom_game_info;
+pub mod custom_game_field;
+pub mod game_rules;
+pub mod leaderboard_info;
+pub mod leaderboard_sort_key;
+pub mod mode_filter;
+pub mod replay_info;
+pub mod saved_game;
+pub mod profile;
+pub mod profile_edit;
+pub mod profiles_info;
+pub mod import_report;
+pub mod race_player;
+pub mod race_player_state;
+pub mod race_answer;
+pub mod race_info;
+pub mod hot_seat_player;
+pub mod hot_seat_setup_info;
@@ -0,0 +1,32 @@
+#[derive(Clone, Copy, PartialEq)]
+pub enum ModeFilter {
+    All,
+    Normal,
+    Hard,
+}
+
+impl ModeFilter {
+    pub fn next(&self) -> ModeFilter {
+        match self {
+            ModeFilter::All => ModeFilter::Normal,
+            ModeFilter::Normal => ModeFilter::Hard,
+            ModeFilter::Hard => ModeFilter::All,
+        }
+    }
+
+    pub fn is_hard_mode(&self) -> Option<bool> {
+        match self {
+            ModeFilter::All => None,
+            ModeFilter::Normal => Some(false),
+            ModeFilter::Hard => Some(true),
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        match self {
+            ModeFilter::All => "all modes",
+            ModeFilter::Normal => "normal mode",
+            ModeFilter::Hard => "hard mode",
+        }
+    }
+}
@@ -0,0 +1,5 @@
+#[derive(Clone, Copy, PartialEq)]
+pub enum ProfileEdit {
+    New,
+    Rename,
+}
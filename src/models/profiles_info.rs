@@ -0,0 +1,9 @@
+use ratatui::widgets::ListState;
+use crate::models::profile_edit::ProfileEdit;
+
+pub struct ProfilesInfo {
+    pub list_state: ListState,
+    pub edit: Option<ProfileEdit>,
+    pub input: String,
+    pub error: Option<String>,
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+use crate::game::GuessOutcome;
+
+/// Answer of the race host to a guess, `Higher` means the number is higher than the guess.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RaceAnswer {
+    Higher,
+    Lower,
+    Correct,
+}
+
+impl RaceAnswer {
+    pub fn outcome(&self) -> GuessOutcome {
+        match self {
+            RaceAnswer::Higher => GuessOutcome::TooLow,
+            RaceAnswer::Lower => GuessOutcome::TooHigh,
+            RaceAnswer::Correct => GuessOutcome::Correct,
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use crate::models::race_player::RacePlayer;
+use crate::race::client::RaceClient;
+
+pub struct RaceInfo {
+    pub client: RaceClient,
+    pub name: String,
+    pub host_port: Option<u16>,
+    pub players: Vec<RacePlayer>,
+    pub expected_players: usize,
+    pub status: Option<String>,
+    /// A guess was sent and the host has not answered it yet.
+    pub is_waiting_for_answer: bool,
+}
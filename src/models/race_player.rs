@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use crate::models::race_player_state::RacePlayerState;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RacePlayer {
+    pub name: String,
+    pub tries: u32,
+    pub state: RacePlayerState,
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RacePlayerState {
+    Playing,
+    Won,
+    Lost,
+}
+
+impl RacePlayerState {
+    pub fn title(&self) -> &str {
+        match self {
+            RacePlayerState::Playing => "playing",
+            RacePlayerState::Won => "won",
+            RacePlayerState::Lost => "lost",
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use std::time::Instant;
+use crate::models::score::Score;
+
+pub struct ReplayInfo {
+    pub score: Score,
+    pub step: usize,
+    pub is_playing: bool,
+    pub playback_started_at: Instant,
+    pub playback_started_from_ms: i64,
+}
@@ -0,0 +1,9 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use crate::game::Game;
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedGame {
+    pub game: Game,
+    pub saved_at: DateTime<Local>,
+}
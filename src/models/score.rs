@@ -1,13 +1,52 @@
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, NaiveDate};
 use serde::{Deserialize, Serialize};
+use crate::models::game_move::GameMove;
+use crate::models::hot_seat_player::HotSeatPlayer;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Score {
     pub name: String,
+    #[serde(default)]
+    pub player_id: Option<String>,
     pub tries: i32,
     pub started_at: DateTime<Local>,
     pub completed_at: DateTime<Local>,
     pub completed_for_ms: i64,
     pub number_range: String,
     pub is_hard_mode: bool,
-}
\ No newline at end of file
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub daily_challenge: Option<NaiveDate>,
+    #[serde(default)]
+    pub points: u32,
+    #[serde(default)]
+    pub first_guess_ms: Option<i64>,
+    #[serde(default)]
+    pub slowest_guess_ms: Option<i64>,
+    #[serde(default)]
+    pub secret_number: Option<i32>,
+    #[serde(default)]
+    pub moves: Vec<GameMove>,
+    #[serde(default)]
+    pub hints_used: u32,
+    /// Guesses of every player of a hot-seat game in turn order, `name` is the winner.
+    #[serde(default)]
+    pub hot_seat_players: Vec<HotSeatPlayer>,
+}
+
+impl Score {
+    /// Short mode for tables: "H" for hard mode and e.g. "3P" for a hot-seat game of three players.
+    pub fn mode_label(&self) -> String {
+        let players = match self.hot_seat_players.len() {
+            0 => String::new(),
+            len => format!("{len}P"),
+        };
+
+        match self.is_hard_mode {
+            true if players.is_empty() => "H".to_string(),
+            true => format!("H {players}"),
+            false => players,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub enum UserInputMode {
     InputNumber,
     InputName,
-}
\ No newline at end of file
+    GameOver,
+}
@@ -0,0 +1,78 @@
+use std::io::{self, BufRead, Write};
+use crate::game::{Game, GuessError, GuessOutcome};
+use crate::profiles::{normalize_name, PlayerProfiles};
+use crate::scores::{create_score, moved_aside_message, ScoreStore};
+
+// Responses go to stdout one per line, prompts go to stderr so the output stays scriptable.
+pub fn run_plain_game(mut game: Game, store: &dyn ScoreStore, mut profiles: PlayerProfiles) -> io::Result<()> {
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    eprintln!("Guess the number {}-{}!", game.info.min_number, game.info.max_number);
+
+    loop {
+        eprint!("Enter your guess: ");
+        io::stderr().flush()?;
+
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => return Ok(()),
+        };
+
+        if line.trim() == "hint" {
+            println!("hint {}", game.use_hint());
+            continue;
+        }
+
+        match game.submit(&line) {
+            GuessOutcome::TooLow => println!("higher"),
+            GuessOutcome::TooHigh => println!("lower"),
+            GuessOutcome::Invalid(error @ GuessError::OutOfRange { .. }) => {
+                println!("out of range");
+                eprintln!("{error}");
+            }
+            GuessOutcome::Invalid(error) => {
+                println!("invalid");
+                eprintln!("{error}");
+            }
+            GuessOutcome::Correct => {
+                println!("correct");
+                break;
+            }
+            GuessOutcome::Lost => {
+                println!("lost {}", game.info.generated_number);
+                return Ok(());
+            }
+        }
+    }
+
+    eprintln!("You won in {} tries! Optimal strategy needs {} tries.", game.tries(), game.optimal_tries());
+    match profiles.active() {
+        Some(profile) => eprint!("Enter your name [{}]: ", profile.name),
+        None => eprint!("Enter your name: "),
+    }
+    io::stderr().flush()?;
+
+    let name = match lines.next() {
+        Some(line) => normalize_name(&line?),
+        None => return Ok(()),
+    };
+
+    let player = match (name.is_empty(), profiles.active()) {
+        (false, _) => profiles.select_or_create(&name),
+        (true, Some(profile)) => profile.clone(),
+        (true, None) => return Ok(()),
+    };
+
+    if let Err(err) = profiles.save() {
+        eprintln!("Could not save player profiles: {err}");
+    }
+
+    match store.add_score(create_score(&player, &game)) {
+        Ok(Some(backup_path)) => eprintln!("{}", moved_aside_message(&backup_path)),
+        Ok(None) => {}
+        Err(err) => eprintln!("{err}"),
+    }
+
+    Ok(())
+}
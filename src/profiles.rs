@@ -0,0 +1,128 @@
+use std::io;
+use std::path::PathBuf;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use crate::models::profile::Profile;
+use crate::models::score::Score;
+use crate::files::{read_json_file_or_move_aside, write_file_atomically};
+use crate::scores::data_dir;
+
+const PROFILES_FILE_NAME: &str = "profiles.json";
+
+/// Player profiles known on this machine and the one that played last.
+#[derive(Default, Serialize, Deserialize)]
+pub struct PlayerProfiles {
+    pub active_player_id: Option<String>,
+    pub profiles: Vec<Profile>,
+}
+
+/// Trims the name and collapses inner whitespace.
+pub fn normalize_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Names that only differ in case or whitespace belong to the same player.
+pub fn name_key(name: &str) -> String {
+    normalize_name(name).to_lowercase()
+}
+
+pub fn profiles_path() -> PathBuf {
+    data_dir().unwrap_or_default().join(PROFILES_FILE_NAME)
+}
+
+/// Unreadable profiles are moved aside like an unreadable saved game.
+pub fn load_profiles() -> io::Result<PlayerProfiles> {
+    Ok(read_json_file_or_move_aside(&profiles_path())?.unwrap_or_default())
+}
+
+impl PlayerProfiles {
+    pub fn save(&self) -> io::Result<()> {
+        write_file_atomically(&profiles_path(), serde_json::to_string_pretty(self)?.as_bytes())
+    }
+
+    pub fn active(&self) -> Option<&Profile> {
+        self.active_player_id.as_ref().and_then(|id| self.find_by_id(id))
+    }
+
+    pub fn find_by_id(&self, id: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|x| x.id == id)
+    }
+
+    pub fn find_by_name(&self, name: &str) -> Option<&Profile> {
+        let key = name_key(name);
+        self.profiles.iter().find(|x| name_key(&x.name) == key)
+    }
+
+    pub fn select(&mut self, id: &str) {
+        if self.find_by_id(id).is_some() {
+            self.active_player_id = Some(id.to_string());
+        }
+    }
+
+    /// Makes the player with this name active, creating a profile for a new name.
+    pub fn select_or_create(&mut self, name: &str) -> Profile {
+        let profile = self.find_or_create(name);
+
+        self.active_player_id = Some(profile.id.clone());
+        profile
+    }
+
+    /// Like `select_or_create`, but keeps the active player.
+    pub fn find_or_create(&mut self, name: &str) -> Profile {
+        if let Some(profile) = self.find_by_name(name) {
+            return profile.clone();
+        }
+
+        let profile = Profile {
+            id: format!("{:016x}", rand::random::<u64>()),
+            name: normalize_name(name),
+            created_at: Local::now(),
+        };
+
+        self.profiles.push(profile.clone());
+        profile
+    }
+
+    pub fn rename(&mut self, id: &str, new_name: &str) -> Result<(), String> {
+        let new_name = normalize_name(new_name);
+
+        if new_name.is_empty() {
+            return Err("Name must not be empty".to_string());
+        }
+
+        if self.find_by_name(&new_name).is_some_and(|x| x.id != id) {
+            return Err(format!("There is already a player called '{new_name}'"));
+        }
+
+        match self.profiles.iter_mut().find(|x| x.id == id) {
+            Some(profile) => {
+                profile.name = new_name;
+                Ok(())
+            }
+            None => Err("Player not found".to_string()),
+        }
+    }
+
+    /// Links scores from another machine to local players with the same name, player ids from there mean nothing here.
+    pub fn adopt_scores(&self, scores: &mut [Score]) {
+        for score in scores.iter_mut() {
+            if score.player_id.as_ref().and_then(|id| self.find_by_id(id)).is_none() {
+                score.player_id = self.find_by_name(&score.name).map(|x| x.id.clone());
+            }
+        }
+    }
+
+    /// Shows scores under the current name of their player, so renaming keeps the history together.
+    pub fn apply_names(&self, scores: &mut [Score]) {
+        for score in scores.iter_mut() {
+            let profile = match &score.player_id {
+                Some(id) => self.find_by_id(id),
+                None => self.find_by_name(&score.name),
+            };
+
+            if let Some(profile) = profile {
+                score.name = profile.name.clone();
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::io::{self, BufRead, BufReader};
+use std::net::{Shutdown, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+use crate::race::protocol::{write_message, ClientMessage, HostMessage};
+
+/// Connection of a player to the race host, messages from the host are read in the background.
+pub struct RaceClient {
+    writer: TcpStream,
+    messages: Receiver<HostMessage>,
+}
+
+impl RaceClient {
+    pub fn connect(address: &str, name: &str) -> io::Result<RaceClient> {
+        let mut writer = TcpStream::connect(address)?;
+        let reader = writer.try_clone()?;
+        let (sender, messages) = mpsc::channel();
+
+        thread::spawn(move || read_messages(reader, sender));
+        write_message(&mut writer, &ClientMessage::Join { name: name.to_string() })?;
+
+        Ok(RaceClient { writer, messages })
+    }
+
+    pub fn send(&mut self, message: &ClientMessage) -> io::Result<()> {
+        write_message(&mut self.writer, message)
+    }
+
+    /// `TryRecvError::Disconnected` means the host closed the connection.
+    pub fn try_recv(&self) -> Result<HostMessage, TryRecvError> {
+        self.messages.try_recv()
+    }
+}
+
+impl Drop for RaceClient {
+    fn drop(&mut self) {
+        // The reading thread keeps its own handle, so the socket has to be closed explicitly.
+        let _ = self.writer.shutdown(Shutdown::Both);
+    }
+}
+
+fn read_messages(stream: TcpStream, messages: Sender<HostMessage>) {
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            return;
+        };
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let message = serde_json::from_str(&line).unwrap_or_else(|err| HostMessage::Error {
+            message: format!("The host sent an invalid message: {err}"),
+        });
+
+        if messages.send(message).is_err() {
+            return;
+        }
+    }
+}
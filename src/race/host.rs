@@ -0,0 +1,273 @@
+use std::cmp::Ordering;
+use std::io::{self, BufRead, BufReader};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use crate::game::Game;
+use crate::models::game_rules::GameRules;
+use crate::models::race_answer::RaceAnswer;
+use crate::models::race_player::RacePlayer;
+use crate::models::race_player_state::RacePlayerState;
+use crate::profiles::{name_key, normalize_name};
+use crate::race::protocol::{write_message, ClientMessage, HostMessage};
+
+pub struct RaceSettings {
+    pub min_number: i32,
+    pub max_number: i32,
+    pub seed: u64,
+    pub expected_players: usize,
+}
+
+enum HostEvent {
+    Connected(usize, TcpStream),
+    Message(usize, serde_json::Result<ClientMessage>),
+    Disconnected(usize),
+}
+
+struct Connection {
+    id: usize,
+    // Players who left a started race stay in the standings without a connection.
+    writer: Option<TcpStream>,
+    player: Option<RacePlayer>,
+}
+
+/// Listens for players in the background, returns the address the host is listening on.
+pub fn start_host(port: u16, settings: RaceSettings) -> io::Result<SocketAddr> {
+    let listener = TcpListener::bind(("0.0.0.0", port))?;
+    let address = listener.local_addr()?;
+    let (events, receiver) = mpsc::channel();
+
+    // Only the host knows the number, players get answers to their guesses.
+    let number = Game::from_seed(settings.min_number..=settings.max_number, GameRules::default(), settings.seed)
+        .info.generated_number;
+
+    thread::spawn(move || accept_connections(listener, events));
+    thread::spawn(move || RaceHost { settings, number, connections: Vec::new(), is_started: false }.run(receiver));
+
+    Ok(address)
+}
+
+fn accept_connections(listener: TcpListener, events: Sender<HostEvent>) {
+    for (id, stream) in listener.incoming().enumerate() {
+        let Ok((stream, reader)) = stream.and_then(|x| x.try_clone().map(|reader| (x, reader))) else {
+            continue;
+        };
+
+        if events.send(HostEvent::Connected(id, stream)).is_err() {
+            return;
+        }
+
+        let events = events.clone();
+        thread::spawn(move || read_messages(id, reader, events));
+    }
+}
+
+fn read_messages(id: usize, stream: TcpStream, events: Sender<HostEvent>) {
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+
+        if !line.trim().is_empty() && events.send(HostEvent::Message(id, serde_json::from_str(&line))).is_err() {
+            return;
+        }
+    }
+
+    let _ = events.send(HostEvent::Disconnected(id));
+}
+
+struct RaceHost {
+    settings: RaceSettings,
+    number: i32,
+    connections: Vec<Connection>,
+    is_started: bool,
+}
+
+impl RaceHost {
+    fn run(mut self, events: Receiver<HostEvent>) {
+        for event in events {
+            let is_finished = match event {
+                HostEvent::Connected(id, stream) => self.connect(id, stream),
+                HostEvent::Message(id, Ok(message)) => self.handle_message(id, message),
+                HostEvent::Message(id, Err(err)) => {
+                    self.send(id, &HostMessage::Error { message: format!("Invalid message: {err}") });
+                    false
+                }
+                HostEvent::Disconnected(id) => self.disconnect(id),
+            };
+
+            if is_finished {
+                return;
+            }
+        }
+    }
+
+    fn connect(&mut self, id: usize, mut stream: TcpStream) -> bool {
+        if self.is_started {
+            let _ = write_message(&mut stream, &HostMessage::Error { message: "The race has already started".to_string() });
+            let _ = stream.shutdown(Shutdown::Both);
+            return false;
+        }
+
+        self.connections.push(Connection { id, writer: Some(stream), player: None });
+        false
+    }
+
+    /// Returns `true` once the race is over.
+    fn handle_message(&mut self, id: usize, message: ClientMessage) -> bool {
+        let is_started = self.is_started;
+        let Some(connection) = self.connections.iter().find(|x| x.id == id) else {
+            return false;
+        };
+
+        match (message, &connection.player) {
+            (ClientMessage::Join { name }, None) => {
+                self.join(id, &name);
+                false
+            }
+            (ClientMessage::Join { .. }, Some(_)) => {
+                self.send(id, &HostMessage::Error { message: "You have already joined the race".to_string() });
+                false
+            }
+            (_, None) => {
+                self.send(id, &HostMessage::Error { message: "Join the race first".to_string() });
+                false
+            }
+            (_, Some(_)) if !is_started => {
+                self.send(id, &HostMessage::Error { message: "The race has not started yet".to_string() });
+                false
+            }
+            (ClientMessage::Guess { value }, Some(_)) => self.guess(id, value),
+        }
+    }
+
+    fn guess(&mut self, id: usize, value: i32) -> bool {
+        let (min_number, max_number) = (self.settings.min_number, self.settings.max_number);
+
+        if value < min_number || value > max_number {
+            self.send(id, &HostMessage::Error { message: format!("The number must be in {min_number}-{max_number}") });
+            return false;
+        }
+
+        let answer = match value.cmp(&self.number) {
+            Ordering::Less => RaceAnswer::Higher,
+            Ordering::Greater => RaceAnswer::Lower,
+            Ordering::Equal => RaceAnswer::Correct,
+        };
+
+        let Some(player) = self.connections.iter_mut().find(|x| x.id == id).and_then(|x| x.player.as_mut()) else {
+            return false;
+        };
+
+        player.tries += 1;
+        if answer == RaceAnswer::Correct {
+            player.state = RacePlayerState::Won;
+        }
+        let (winner, tries) = (player.name.clone(), player.tries);
+
+        self.send(id, &HostMessage::Answer { value, answer });
+        self.broadcast_standings();
+
+        if answer == RaceAnswer::Correct {
+            self.broadcast(&HostMessage::Finished { winner: Some(winner), tries: Some(tries), number: self.number });
+        }
+
+        answer == RaceAnswer::Correct
+    }
+
+    fn join(&mut self, id: usize, name: &str) {
+        let mut name = match normalize_name(name) {
+            name if name.is_empty() => "Player".to_string(),
+            name => name,
+        };
+        let base_name = name.clone();
+
+        for suffix in 2.. {
+            if !self.players().any(|x| name_key(&x.name) == name_key(&name)) {
+                break;
+            }
+            name = format!("{base_name} {suffix}");
+        }
+
+        if let Some(connection) = self.connections.iter_mut().find(|x| x.id == id) {
+            connection.player = Some(RacePlayer { name: name.clone(), tries: 0, state: RacePlayerState::Playing });
+        }
+
+        self.send(id, &HostMessage::Welcome { name });
+        self.broadcast_lobby();
+
+        if self.players().count() == self.settings.expected_players {
+            self.is_started = true;
+            self.broadcast(&HostMessage::Start {
+                min_number: self.settings.min_number,
+                max_number: self.settings.max_number,
+            });
+        }
+    }
+
+    fn disconnect(&mut self, id: usize) -> bool {
+        if !self.is_started {
+            let had_joined = self.connections.iter().any(|x| x.id == id && x.player.is_some());
+            self.connections.retain(|x| x.id != id);
+
+            if had_joined {
+                self.broadcast_lobby();
+            }
+            return false;
+        }
+
+        let Some(connection) = self.connections.iter_mut().find(|x| x.id == id) else {
+            return false;
+        };
+        connection.writer = None;
+
+        match &mut connection.player {
+            Some(player) if player.state == RacePlayerState::Playing => {
+                player.state = RacePlayerState::Lost;
+                self.broadcast_standings();
+                self.finish_if_everyone_lost()
+            }
+            _ => false,
+        }
+    }
+
+    fn finish_if_everyone_lost(&mut self) -> bool {
+        let is_everyone_lost = self.players().all(|x| x.state == RacePlayerState::Lost);
+
+        if is_everyone_lost {
+            self.broadcast(&HostMessage::Finished { winner: None, tries: None, number: self.number });
+        }
+
+        is_everyone_lost
+    }
+
+    fn players(&self) -> impl Iterator<Item = &RacePlayer> {
+        self.connections.iter().filter_map(|x| x.player.as_ref())
+    }
+
+    fn broadcast_lobby(&mut self) {
+        self.broadcast(&HostMessage::Lobby {
+            players: self.players().map(|x| x.name.clone()).collect(),
+            expected_players: self.settings.expected_players,
+        });
+    }
+
+    fn broadcast_standings(&mut self) {
+        self.broadcast(&HostMessage::Standings { players: self.players().cloned().collect() });
+    }
+
+    fn send(&mut self, id: usize, message: &HostMessage) {
+        if let Some(writer) = self.connections.iter_mut().find(|x| x.id == id).and_then(|x| x.writer.as_mut()) {
+            // A failed write shows up as a disconnect on the reading side.
+            let _ = write_message(writer, message);
+        }
+    }
+
+    fn broadcast(&mut self, message: &HostMessage) {
+        for connection in self.connections.iter_mut().filter(|x| x.player.is_some()) {
+            if let Some(writer) = connection.writer.as_mut() {
+                let _ = write_message(writer, message);
+            }
+        }
+    }
+}
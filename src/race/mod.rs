@@ -0,0 +1,144 @@
+pub mod client;
+pub mod host;
+pub mod protocol;
+
+pub const DEFAULT_RACE_PORT: u16 = 7878;
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::{SocketAddr, TcpStream};
+    use std::time::Duration;
+    use serde_json::{json, Value};
+    use super::host::{start_host, RaceSettings};
+
+    struct TestPlayer {
+        reader: BufReader<TcpStream>,
+        writer: TcpStream,
+    }
+
+    impl TestPlayer {
+        fn connect(address: SocketAddr) -> TestPlayer {
+            let writer = TcpStream::connect(("127.0.0.1", address.port())).unwrap();
+            writer.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+
+            TestPlayer { reader: BufReader::new(writer.try_clone().unwrap()), writer }
+        }
+
+        fn send(&mut self, message: Value) {
+            writeln!(self.writer, "{message}").unwrap();
+        }
+
+        fn receive(&mut self) -> Value {
+            let mut line = String::new();
+            self.reader.read_line(&mut line).unwrap();
+            serde_json::from_str(&line).unwrap()
+        }
+
+        fn join(address: SocketAddr, name: &str) -> TestPlayer {
+            let mut player = TestPlayer::connect(address);
+            player.send(json!({"type": "join", "name": name}));
+            player
+        }
+    }
+
+    // Seed 42 picks 38 in 1..=100.
+    fn host(expected_players: usize) -> SocketAddr {
+        start_host(0, RaceSettings { min_number: 1, max_number: 100, seed: 42, expected_players }).unwrap()
+    }
+
+    #[test]
+    fn race_starts_when_everyone_joined_and_host_declares_first_correct_guess() {
+        let address = host(2);
+
+        let mut alice = TestPlayer::join(address, "alice");
+        assert_eq!(alice.receive(), json!({"type": "welcome", "name": "alice"}));
+        assert_eq!(alice.receive(), json!({"type": "lobby", "players": ["alice"], "expected_players": 2}));
+
+        let mut bob = TestPlayer::join(address, "bob");
+        assert_eq!(bob.receive(), json!({"type": "welcome", "name": "bob"}));
+
+        let start = json!({"type": "start", "min_number": 1, "max_number": 100});
+        for player in [&mut alice, &mut bob] {
+            assert_eq!(player.receive()["players"], json!(["alice", "bob"]));
+            assert_eq!(player.receive(), start);
+        }
+
+        bob.send(json!({"type": "guess", "value": 50}));
+        assert_eq!(bob.receive(), json!({"type": "answer", "value": 50, "answer": "lower"}));
+        assert_eq!(alice.receive(), json!({"type": "standings", "players": [
+            {"name": "alice", "tries": 0, "state": "playing"},
+            {"name": "bob", "tries": 1, "state": "playing"},
+        ]}));
+
+        alice.send(json!({"type": "guess", "value": 20}));
+        assert_eq!(alice.receive(), json!({"type": "answer", "value": 20, "answer": "higher"}));
+        alice.receive();
+
+        alice.send(json!({"type": "guess", "value": 38}));
+        assert_eq!(alice.receive(), json!({"type": "answer", "value": 38, "answer": "correct"}));
+        assert_eq!(alice.receive()["players"][0]["state"], "won");
+        assert_eq!(alice.receive(), json!({"type": "finished", "winner": "alice", "tries": 2, "number": 38}));
+    }
+
+    #[test]
+    fn claiming_a_win_or_guessing_out_of_range_is_rejected() {
+        let address = host(1);
+
+        let mut alice = TestPlayer::join(address, "alice");
+        for _ in 0..3 {
+            alice.receive();
+        }
+
+        alice.send(json!({"type": "won", "tries": 1}));
+        assert_eq!(alice.receive()["type"], "error");
+
+        alice.send(json!({"type": "guess", "value": 101}));
+        assert_eq!(alice.receive(), json!({"type": "error", "message": "The number must be in 1-100"}));
+
+        alice.send(json!({"type": "guess", "value": 40}));
+        assert_eq!(alice.receive()["answer"], "lower");
+        assert_eq!(alice.receive()["players"][0]["tries"], 1);
+    }
+
+    #[test]
+    fn duplicate_names_get_a_suffix_and_late_players_are_rejected() {
+        let address = host(2);
+
+        let mut first = TestPlayer::join(address, "alice");
+        assert_eq!(first.receive()["name"], "alice");
+
+        let mut second = TestPlayer::join(address, " Alice ");
+        assert_eq!(second.receive()["name"], "Alice 2");
+
+        let mut late = TestPlayer::connect(address);
+        assert_eq!(late.receive(), json!({"type": "error", "message": "The race has already started"}));
+    }
+
+    #[test]
+    fn players_that_leave_lose_and_the_race_goes_on() {
+        let address = host(3);
+
+        let mut alice = TestPlayer::join(address, "alice");
+        alice.receive();
+        alice.receive();
+
+        let bob = TestPlayer::join(address, "bob");
+        alice.receive();
+
+        let carol = TestPlayer::join(address, "carol");
+        alice.receive();
+        alice.receive();
+
+        drop(bob);
+        assert_eq!(alice.receive()["players"][1]["state"], "lost");
+
+        drop(carol);
+        assert_eq!(alice.receive()["players"][2]["state"], "lost");
+
+        alice.send(json!({"type": "guess", "value": 38}));
+        assert_eq!(alice.receive()["answer"], "correct");
+        alice.receive();
+        assert_eq!(alice.receive(), json!({"type": "finished", "winner": "alice", "tries": 1, "number": 38}));
+    }
+}
@@ -0,0 +1,58 @@
+//! Messages of a race, every message is a JSON object on its own line with a `type` field.
+//!
+//! A player connects and sends `join`, the host answers with `welcome` (the name may get a
+//! suffix if it is taken) and sends `lobby` to everyone whenever the player list changes.
+//! Once the expected number of players joined, the host sends `start` with the range. Only the
+//! host knows the number: a player sends every guess with `guess` and gets an `answer` saying
+//! whether the number is `higher`, `lower` or `correct`. The host sends `standings` after every
+//! guess and `finished` with the number when the first player guessed it (or everyone left).
+//! Players that disconnect lose.
+//!
+//! ```text
+//! -> {"type":"join","name":"alice"}
+//! <- {"type":"welcome","name":"alice"}
+//! <- {"type":"lobby","players":["alice","bob"],"expected_players":2}
+//! <- {"type":"start","min_number":1,"max_number":100}
+//! -> {"type":"guess","value":50}
+//! <- {"type":"answer","value":50,"answer":"lower"}
+//! <- {"type":"standings","players":[{"name":"alice","tries":1,"state":"playing"},{"name":"bob","tries":0,"state":"playing"}]}
+//! -> {"type":"guess","value":25}
+//! <- {"type":"answer","value":25,"answer":"correct"}
+//! <- {"type":"standings","players":[{"name":"alice","tries":2,"state":"won"},{"name":"bob","tries":0,"state":"playing"}]}
+//! <- {"type":"finished","winner":"alice","tries":2,"number":25}
+//! ```
+//!
+//! A message the host can not accept (the race has started, the guess is out of the range or
+//! the message is not valid) gets `{"type":"error","message":"..."}`.
+
+use std::io::{self, Write};
+use serde::{Deserialize, Serialize};
+use crate::models::race_answer::RaceAnswer;
+use crate::models::race_player::RacePlayer;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Join { name: String },
+    Guess { value: i32 },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HostMessage {
+    Welcome { name: String },
+    Lobby { players: Vec<String>, expected_players: usize },
+    Start { min_number: i32, max_number: i32 },
+    Answer { value: i32, answer: RaceAnswer },
+    Standings { players: Vec<RacePlayer> },
+    Finished { winner: Option<String>, tries: Option<u32>, number: i32 },
+    Error { message: String },
+}
+
+pub fn write_message<W: Write, T: Serialize>(writer: &mut W, message: &T) -> io::Result<()> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+
+    writer.write_all(line.as_bytes())?;
+    writer.flush()
+}
@@ -0,0 +1,28 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use crate::files::{read_json_file_or_move_aside, write_file_atomically};
+use crate::models::saved_game::SavedGame;
+use crate::scores::data_dir;
+
+const SAVED_GAME_FILE_NAME: &str = "saved_game.json";
+
+pub fn saved_game_path() -> PathBuf {
+    data_dir().unwrap_or_default().join(SAVED_GAME_FILE_NAME)
+}
+
+/// An unreadable saved game is moved aside, so it does not block saving the next one.
+pub fn load_game() -> io::Result<Option<SavedGame>> {
+    read_json_file_or_move_aside(&saved_game_path())
+}
+
+pub fn remove_saved_game() -> io::Result<()> {
+    match fs::remove_file(saved_game_path()) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+pub fn save_game(saved_game: &SavedGame) -> io::Result<()> {
+    write_file_atomically(&saved_game_path(), serde_json::to_string(saved_game)?.as_bytes())
+}
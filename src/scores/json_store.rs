@@ -0,0 +1,177 @@
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use fs4::FileExt;
+use crate::files::{move_aside_corrupted, write_file_atomically};
+use crate::models::import_report::ImportReport;
+use crate::models::score::Score;
+use crate::scores::merge::merge_scores;
+use crate::scores::schema::{parse_score_file, score_file_to_json, SchemaError};
+use crate::scores::{prepare_loaded_scores, sort_scores, ScoreQuery, ScoreStore, ScoreStoreError};
+
+const LEADERBOARD_FILE_NAME: &str = "scores.json";
+
+pub struct JsonScoreStore {
+    path: PathBuf,
+}
+
+impl JsonScoreStore {
+    pub fn new(path: PathBuf) -> JsonScoreStore {
+        JsonScoreStore { path }
+    }
+
+    /// Uses `scores.json` in the data directory, moving a `./scores.json` left by older versions there.
+    pub fn open_default(data_dir: Option<PathBuf>) -> Result<JsonScoreStore, ScoreStoreError> {
+        let Some(data_dir) = data_dir else {
+            return Ok(JsonScoreStore::new(PathBuf::from(LEADERBOARD_FILE_NAME)));
+        };
+
+        let store = JsonScoreStore::new(data_dir.join(LEADERBOARD_FILE_NAME));
+        store.migrate_local_scores_file()?;
+
+        Ok(store)
+    }
+
+    // The lock lives in a separate file, so it stays valid while the leaderboard file is replaced.
+    fn with_store_lock<T>(&self, action: impl FnOnce() -> Result<T, ScoreStoreError>) -> Result<T, ScoreStoreError> {
+        if let Some(parent) = self.path.parent().filter(|x| !x.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+
+        let lock_file = File::create(self.sibling_path("lock"))?;
+        lock_file.lock_exclusive()?;
+
+        let result = action();
+
+        FileExt::unlock(&lock_file)?;
+        result
+    }
+
+    fn read_scores(&self) -> Result<Vec<Score>, ScoreStoreError> {
+        let json = match fs::read_to_string(&self.path) {
+            Ok(json) => json,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+
+        match parse_score_file(&json) {
+            Ok(scores) => Ok(prepare_loaded_scores(scores)),
+            Err(SchemaError::NewerVersion(version)) => Err(ScoreStoreError::NewerVersion(version)),
+            Err(SchemaError::Invalid(source)) => {
+                let backup_path = move_aside_corrupted(&self.path)?;
+                Err(ScoreStoreError::Corrupted { source, backup_path })
+            }
+        }
+    }
+
+    fn write_scores(&self, scores: &[Score]) -> Result<(), ScoreStoreError> {
+        let json = score_file_to_json(scores).map_err(ScoreStoreError::Serialize)?;
+
+        Ok(write_file_atomically(&self.path, json.as_bytes())?)
+    }
+
+    fn sibling_path(&self, suffix: &str) -> PathBuf {
+        let mut path = self.path.as_os_str().to_owned();
+        path.push(format!(".{suffix}"));
+        PathBuf::from(path)
+    }
+
+    fn migrate_local_scores_file(&self) -> Result<(), ScoreStoreError> {
+        let local_path = Path::new(LEADERBOARD_FILE_NAME);
+
+        if self.path.exists() || !local_path.is_file() {
+            return Ok(());
+        }
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        if fs::rename(local_path, &self.path).is_err() {
+            fs::copy(local_path, &self.path)?;
+            fs::remove_file(local_path)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ScoreStore for JsonScoreStore {
+    fn load_scores(&self, query: &ScoreQuery) -> Result<Vec<Score>, ScoreStoreError> {
+        let scores = self.with_store_lock(|| self.read_scores())?;
+
+        Ok(scores.into_iter()
+            .filter(|score| query.matches(score))
+            .skip(query.offset)
+            .take(query.limit.unwrap_or(usize::MAX))
+            .collect())
+    }
+
+    fn add_score(&self, score: Score) -> Result<Option<PathBuf>, ScoreStoreError> {
+        self.with_store_lock(|| {
+            // The unreadable file is already moved aside, so the new score starts a fresh leaderboard.
+            let (mut scores, backup_path) = match self.read_scores() {
+                Ok(scores) => (scores, None),
+                Err(ScoreStoreError::Corrupted { backup_path, .. }) => (Vec::new(), Some(backup_path)),
+                Err(err) => return Err(err),
+            };
+
+            scores.push(score);
+            sort_scores(&mut scores);
+            self.write_scores(&scores)?;
+
+            Ok(backup_path)
+        })
+    }
+
+    fn clear_scores(&self) -> Result<(), ScoreStoreError> {
+        self.with_store_lock(|| self.write_scores(&[]))
+    }
+
+    fn import_scores(&self, scores: Vec<Score>) -> Result<ImportReport, ScoreStoreError> {
+        self.with_store_lock(|| {
+            let mut existing = self.read_scores()?;
+            let (new_scores, report) = merge_scores(&existing, scores);
+
+            if !new_scores.is_empty() {
+                existing.extend(new_scores);
+                sort_scores(&mut existing);
+                self.write_scores(&existing)?;
+            }
+
+            Ok(report)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use chrono::Local;
+    use super::*;
+    use crate::game::Game;
+    use crate::models::game_rules::GameRules;
+    use crate::models::profile::Profile;
+    use crate::scores::create_score;
+
+    #[test]
+    fn score_is_kept_when_leaderboard_file_is_unreadable() {
+        let dir = env::temp_dir().join(format!("rust-guess-json-store-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let store = JsonScoreStore::new(dir.join(LEADERBOARD_FILE_NAME));
+        fs::write(&store.path, "{ not json").unwrap();
+
+        let mut game = Game::from_seed(1..=100, GameRules::default(), 42);
+        game.guess(game.info.generated_number);
+        let player = Profile { id: "a11ce".to_string(), name: "alice".to_string(), created_at: Local::now() };
+
+        let backup_path = store.add_score(create_score(&player, &game)).unwrap().unwrap();
+        let scores = store.load_scores(&ScoreQuery::default()).unwrap();
+        let backup = fs::read_to_string(&backup_path).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(backup, "{ not json");
+        assert_eq!(scores.len(), 1);
+        assert_eq!(scores[0].name, "alice");
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Local};
+use crate::formatting::format_duration_ms;
+use crate::models::import_report::ImportReport;
+use crate::models::score::Score;
+use crate::profiles::name_key;
+use crate::scores::repair_score;
+
+type ScoreKey = (String, DateTime<Local>, DateTime<Local>, String);
+
+// The same game played on one machine has the same player, start, end and range everywhere.
+fn score_key(score: &Score) -> ScoreKey {
+    (name_key(&score.name), score.started_at, score.completed_at, score.number_range.clone())
+}
+
+fn is_same_result(left: &Score, right: &Score) -> bool {
+    left.tries == right.tries
+        && left.is_hard_mode == right.is_hard_mode
+        && left.completed_for_ms == right.completed_for_ms
+}
+
+/// Returns the scores from `incoming` that are not in `existing` yet. Games that are in both but
+/// with different results are left out and reported as conflicts, the local version wins.
+pub fn merge_scores(existing: &[Score], incoming: Vec<Score>) -> (Vec<Score>, ImportReport) {
+    let mut known: HashMap<ScoreKey, Score> = existing.iter()
+        .map(|score| (score_key(score), score.clone()))
+        .collect();
+    let mut report = ImportReport::default();
+    let mut new_scores = Vec::new();
+
+    for mut score in incoming {
+        repair_score(&mut score);
+
+        match known.get(&score_key(&score)) {
+            Some(local) if is_same_result(local, &score) => report.duplicates += 1,
+            Some(local) => report.conflicts.push(format!(
+                "{} on {} ({}): {} tries in {} here, {} tries in {} in the imported file",
+                score.name, score.started_at.format("%Y-%m-%d %H:%M:%S"), score.number_range,
+                local.tries, format_duration_ms(local.completed_for_ms),
+                score.tries, format_duration_ms(score.completed_for_ms))),
+            None => {
+                known.insert(score_key(&score), score.clone());
+                new_scores.push(score);
+            }
+        }
+    }
+
+    report.added = new_scores.len();
+    (new_scores, report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scores::prepare_loaded_scores;
+    use crate::scores::schema::parse_score_file;
+
+    const SCORES_V0_BASELINE: &str = include_str!("../../tests/fixtures/scores_v0_baseline.json");
+    const SCORES_V1: &str = include_str!("../../tests/fixtures/scores_v1.json");
+
+    #[test]
+    fn identical_games_are_skipped() {
+        let existing = prepare_loaded_scores(parse_score_file(SCORES_V0_BASELINE).unwrap());
+        let mut incoming = parse_score_file(SCORES_V0_BASELINE).unwrap();
+        incoming[0].name = "Alice ".to_string();
+        incoming.extend(parse_score_file(SCORES_V1).unwrap());
+
+        let (new_scores, report) = merge_scores(&existing, incoming);
+
+        assert_eq!(new_scores.len(), 2);
+        assert_eq!((report.added, report.duplicates, report.conflicts.len()), (2, 2, 0));
+    }
+
+    #[test]
+    fn different_results_of_the_same_game_are_conflicts() {
+        let existing = prepare_loaded_scores(parse_score_file(SCORES_V0_BASELINE).unwrap());
+        let mut incoming = parse_score_file(SCORES_V0_BASELINE).unwrap();
+        incoming[1].tries += 1;
+
+        let (new_scores, report) = merge_scores(&existing, incoming);
+
+        assert!(new_scores.is_empty());
+        assert_eq!((report.duplicates, report.conflicts.len()), (1, 1));
+        assert!(report.conflicts[0].starts_with("bob on"));
+    }
+}
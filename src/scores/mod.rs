@@ -0,0 +1,262 @@
+use std::cmp::Reverse;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use crate::game::Game;
+use crate::models::import_report::ImportReport;
+use crate::models::profile::Profile;
+use crate::models::score::Score;
+use crate::profiles::name_key;
+use crate::scoring::compute_points;
+use crate::scores::json_store::JsonScoreStore;
+use crate::scores::schema::{parse_score_file, SchemaError};
+
+pub mod json_store;
+pub mod merge;
+pub mod schema;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_store;
+
+const DATA_DIR_NAME: &str = "rust-guess";
+
+#[derive(Debug)]
+pub enum ScoreStoreError {
+    Io(io::Error),
+    Serialize(serde_json::Error),
+    Corrupted {
+        source: serde_json::Error,
+        backup_path: PathBuf,
+    },
+    NewerVersion(u32),
+    InvalidImport {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[cfg(feature = "sqlite")]
+    Sqlite(rusqlite::Error),
+    #[cfg(not(feature = "sqlite"))]
+    Unsupported(String),
+}
+
+impl fmt::Display for ScoreStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScoreStoreError::Io(err) => write!(f, "Leaderboard file error: {err}"),
+            ScoreStoreError::Serialize(err) => write!(f, "Leaderboard could not be serialized: {err}"),
+            ScoreStoreError::Corrupted { source, backup_path } => write!(f,
+                "Leaderboard file is unreadable ({source}), it was moved to {}", backup_path.display()),
+            ScoreStoreError::NewerVersion(version) => write!(f,
+                "Leaderboard file was written by a newer version of the game (schema version {version})"),
+            ScoreStoreError::InvalidImport { path, source } => write!(f,
+                "{} is not a leaderboard file: {source}", path.display()),
+            #[cfg(feature = "sqlite")]
+            ScoreStoreError::Sqlite(err) => write!(f, "Leaderboard database error: {err}"),
+            #[cfg(not(feature = "sqlite"))]
+            ScoreStoreError::Unsupported(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for ScoreStoreError {}
+
+impl From<io::Error> for ScoreStoreError {
+    fn from(err: io::Error) -> Self {
+        ScoreStoreError::Io(err)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for ScoreStoreError {
+    fn from(err: rusqlite::Error) -> Self {
+        ScoreStoreError::Sqlite(err)
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum StoreKind {
+    Json,
+    Sqlite,
+}
+
+#[derive(Default)]
+pub struct ScoreQuery {
+    pub number_range: Option<String>,
+    pub is_hard_mode: Option<bool>,
+    pub player: Option<String>,
+    /// Scores saved with a player id are matched by it, older ones by the `player` name.
+    pub player_id: Option<String>,
+    pub daily_challenge: Option<NaiveDate>,
+    pub completed_on: Option<NaiveDate>,
+    /// Skips this many of the best scores and returns at most `limit` of the rest.
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl ScoreQuery {
+    pub fn matches(&self, score: &Score) -> bool {
+        self.number_range.as_ref().map_or(true, |range| &score.number_range == range)
+            && self.is_hard_mode.map_or(true, |is_hard_mode| score.is_hard_mode == is_hard_mode)
+            && self.player.as_ref().map_or(true, |player| match (&self.player_id, &score.player_id) {
+                (Some(player_id), Some(score_player_id)) => player_id == score_player_id,
+                _ => name_key(&score.name) == name_key(player),
+            })
+            && self.daily_challenge.map_or(true, |date| score.daily_challenge == Some(date))
+            && self.completed_on.map_or(true, |date| score.completed_at.date_naive() == date)
+    }
+}
+
+/// Scores are returned ordered by points, best first.
+pub trait ScoreStore {
+    fn load_scores(&self, query: &ScoreQuery) -> Result<Vec<Score>, ScoreStoreError>;
+    /// Returns where an unreadable leaderboard was moved, the score is saved to a new one then.
+    fn add_score(&self, score: Score) -> Result<Option<PathBuf>, ScoreStoreError>;
+    fn clear_scores(&self) -> Result<(), ScoreStoreError>;
+    /// Adds the scores that are not in the store yet, see `merge::merge_scores`.
+    fn import_scores(&self, scores: Vec<Score>) -> Result<ImportReport, ScoreStoreError>;
+}
+
+/// Directory of the leaderboard, saved game and player profiles.
+pub fn data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|x| x.join(DATA_DIR_NAME))
+}
+
+/// Opens the leaderboard: an explicit path (`--scores-file` or `RUST_GUESS_SCORES`) wins,
+/// otherwise the store lives in the data directory.
+pub fn open_store(kind: StoreKind, path_override: Option<PathBuf>) -> Result<Box<dyn ScoreStore>, ScoreStoreError> {
+    match kind {
+        StoreKind::Json => {
+            let store = match path_override {
+                Some(path) => JsonScoreStore::new(path),
+                None => JsonScoreStore::open_default(data_dir())?,
+            };
+
+            Ok(Box::new(store))
+        }
+        #[cfg(feature = "sqlite")]
+        StoreKind::Sqlite => {
+            let path = path_override
+                .unwrap_or_else(|| data_dir().unwrap_or_default().join(sqlite_store::DATABASE_FILE_NAME));
+
+            Ok(Box::new(sqlite_store::SqliteScoreStore::open(&path)?))
+        }
+        #[cfg(not(feature = "sqlite"))]
+        StoreKind::Sqlite => Err(ScoreStoreError::Unsupported(
+            "SQLite storage is not available, rebuild with `--features sqlite`".to_string())),
+    }
+}
+
+pub fn moved_aside_message(backup_path: &Path) -> String {
+    format!("Leaderboard file was unreadable, it was moved to {} and a new one was started", backup_path.display())
+}
+
+/// Reads a leaderboard file of any known version, e.g. one copied from another machine.
+pub fn read_score_file(path: &Path) -> Result<Vec<Score>, ScoreStoreError> {
+    let json = fs::read_to_string(path)?;
+
+    match parse_score_file(&json) {
+        Ok(scores) => Ok(scores),
+        Err(SchemaError::NewerVersion(version)) => Err(ScoreStoreError::NewerVersion(version)),
+        Err(SchemaError::Invalid(source)) => Err(ScoreStoreError::InvalidImport { path: path.to_path_buf(), source }),
+    }
+}
+
+pub fn create_score(player: &Profile, game: &Game) -> Score {
+    let info = &game.info;
+    let move_durations: Vec<i64> = game.move_durations().iter().map(|x| x.num_milliseconds()).collect();
+
+    let mut score = Score {
+        name: player.name.clone(),
+        player_id: Some(player.id.clone()),
+        // A hot-seat game counts the guesses of the whole table, every guess narrowed the range for the winner.
+        tries: game.tries() as i32,
+        started_at: info.game_started_at,
+        completed_at: info.game_completed_at,
+        completed_for_ms: game.elapsed().num_milliseconds(),
+        number_range: format!("{}-{}", info.min_number, info.max_number),
+        is_hard_mode: info.rules.is_hard_mode,
+        seed: info.seed,
+        daily_challenge: info.daily_challenge,
+        points: 0,
+        first_guess_ms: move_durations.first().copied(),
+        slowest_guess_ms: move_durations.iter().max().copied(),
+        secret_number: Some(info.generated_number),
+        moves: game.history.clone(),
+        hints_used: info.hints_used,
+        hot_seat_players: game.player_tries(),
+    };
+
+    score.points = compute_points(&score);
+    score
+}
+
+/// Fills in values missing from scores saved by older versions.
+fn repair_score(score: &mut Score) {
+    // Older versions subtracted times of day, which goes negative for games crossing midnight.
+    if score.completed_for_ms < 0 {
+        score.completed_for_ms = (score.completed_at - score.started_at).num_milliseconds();
+        score.points = 0;
+    }
+
+    if score.points == 0 {
+        score.points = compute_points(score);
+    }
+}
+
+fn sort_scores(scores: &mut [Score]) {
+    scores.sort_by_key(|entry| (Reverse(entry.points), entry.tries));
+}
+
+fn prepare_loaded_scores(mut scores: Vec<Score>) -> Vec<Score> {
+    scores.iter_mut().for_each(repair_score);
+    sort_scores(&mut scores);
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Local;
+    use super::*;
+    use crate::models::game_rules::GameRules;
+    use crate::scores::schema::parse_score_file;
+
+    const SCORES_V0_BASELINE: &str = include_str!("../../tests/fixtures/scores_v0_baseline.json");
+
+    #[test]
+    fn scores_of_older_versions_are_repaired() {
+        let mut scores = parse_score_file(SCORES_V0_BASELINE).unwrap();
+        scores[0].points = 123;
+        scores.iter_mut().for_each(repair_score);
+        let (kept, crossed_midnight) = (&scores[0], &scores[1]);
+
+        // Points that were saved are kept, the game time that went negative is taken from the timestamps.
+        assert_eq!((kept.completed_for_ms, kept.points), (37_530, 123));
+        assert_eq!(crossed_midnight.completed_for_ms, 10_000);
+        assert_eq!(crossed_midnight.points, compute_points(crossed_midnight));
+        assert!(crossed_midnight.points > 0);
+    }
+
+    #[test]
+    fn hot_seat_winner_is_scored_on_guesses_of_whole_table() {
+        let mut game = Game::from_seed(1..=100, GameRules::default(), 42);
+        game.info.generated_number = 60;
+        game.info.players = vec!["ann".to_string(), "bob".to_string(), "cid".to_string()];
+
+        for guess in [10, 90, 20, 80, 60] {
+            game.guess(guess);
+        }
+
+        let winner = Profile { id: "b0b".to_string(), name: "bob".to_string(), created_at: Local::now() };
+        let score = create_score(&winner, &game);
+
+        assert_eq!(game.winner(), Some("bob"));
+        assert_eq!(score.tries, 5);
+        assert_eq!(score.hot_seat_players.iter().map(|x| x.tries).collect::<Vec<_>>(), vec![2, 2, 1]);
+
+        let mut single_player_score = score.clone();
+        single_player_score.hot_seat_players.clear();
+        assert_eq!(score.points, compute_points(&single_player_score));
+    }
+}
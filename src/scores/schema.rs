@@ -0,0 +1,186 @@
+//! Layout of the leaderboard file.
+//!
+//! Version 0 is the bare JSON array of scores written by older releases, later versions wrap
+//! the scores in an object with a `version` field. Loading runs the document through
+//! `MIGRATIONS` one version at a time until it reaches `CURRENT_SCHEMA_VERSION`.
+//!
+//! Every new score field bumps the version, even one with a default, so an older build refuses
+//! the file instead of dropping the field the next time it saves.
+//!
+//! - 1: scores wrapped in `{"version": 1, "scores": [...]}`
+//! - 2: `hot_seat_players` on scores of hot-seat games
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use crate::models::score::Score;
+
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+type Migration = fn(Value) -> Value;
+
+// `MIGRATIONS[n]` upgrades a version `n` document to version `n + 1`.
+const MIGRATIONS: [Migration; CURRENT_SCHEMA_VERSION as usize] = [
+    wrap_scores_array,
+    add_hot_seat_players,
+];
+
+#[derive(Serialize, Deserialize)]
+pub struct ScoreFile {
+    pub version: u32,
+    pub scores: Vec<Score>,
+}
+
+#[derive(Serialize)]
+struct ScoreFileRef<'a> {
+    version: u32,
+    scores: &'a [Score],
+}
+
+#[derive(Debug)]
+pub enum SchemaError {
+    Invalid(serde_json::Error),
+    NewerVersion(u32),
+}
+
+impl From<serde_json::Error> for SchemaError {
+    fn from(err: serde_json::Error) -> Self {
+        SchemaError::Invalid(err)
+    }
+}
+
+pub fn parse_score_file(json: &str) -> Result<Vec<Score>, SchemaError> {
+    let document = migrate(serde_json::from_str(json)?)?;
+    let score_file: ScoreFile = serde_json::from_value(document)?;
+
+    Ok(score_file.scores)
+}
+
+pub fn score_file_to_json(scores: &[Score]) -> serde_json::Result<String> {
+    serde_json::to_string(&ScoreFileRef {
+        version: CURRENT_SCHEMA_VERSION,
+        scores,
+    })
+}
+
+pub fn document_version(document: &Value) -> Result<u32, SchemaError> {
+    match document {
+        Value::Array(_) => Ok(0),
+        Value::Object(object) => object.get("version")
+            .and_then(Value::as_u64)
+            .map(|version| version as u32)
+            .ok_or_else(|| invalid("the `version` field is missing")),
+        _ => Err(invalid("expected a list of scores or a versioned score file")),
+    }
+}
+
+pub fn migrate(mut document: Value) -> Result<Value, SchemaError> {
+    let version = document_version(&document)?;
+
+    if version > CURRENT_SCHEMA_VERSION {
+        return Err(SchemaError::NewerVersion(version));
+    }
+
+    for migration in &MIGRATIONS[version as usize..] {
+        document = migration(document);
+    }
+
+    Ok(document)
+}
+
+fn wrap_scores_array(document: Value) -> Value {
+    json!({
+        "version": 1,
+        "scores": document,
+    })
+}
+
+// Older scores have no hot-seat players, which is what the missing field defaults to.
+fn add_hot_seat_players(mut document: Value) -> Value {
+    document["version"] = json!(2);
+    document
+}
+
+fn invalid(message: &str) -> SchemaError {
+    SchemaError::Invalid(serde::de::Error::custom(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCORES_V0_BASELINE: &str = include_str!("../../tests/fixtures/scores_v0_baseline.json");
+    const SCORES_V0_REPLAYS: &str = include_str!("../../tests/fixtures/scores_v0_replays.json");
+    const SCORES_V1: &str = include_str!("../../tests/fixtures/scores_v1.json");
+    const SCORES_V2: &str = include_str!("../../tests/fixtures/scores_v2.json");
+
+    fn round_trip(json: &str) -> Vec<Score> {
+        let scores = parse_score_file(json).unwrap();
+        let saved = score_file_to_json(&scores).unwrap();
+
+        assert_eq!(document_version(&serde_json::from_str(&saved).unwrap()).unwrap(), CURRENT_SCHEMA_VERSION);
+
+        let reloaded = parse_score_file(&saved).unwrap();
+        assert_eq!(serde_json::to_value(&scores).unwrap(), serde_json::to_value(&reloaded).unwrap());
+
+        reloaded
+    }
+
+    #[test]
+    fn v0_baseline_array_is_migrated() {
+        let scores = round_trip(SCORES_V0_BASELINE);
+
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores[0].name, "alice");
+        assert_eq!(scores[0].tries, 7);
+        assert_eq!(scores[0].number_range, "1-100");
+        assert_eq!(scores[1].completed_for_ms, -86_390_000);
+        assert!(scores.iter().all(|x| x.seed.is_none() && x.player_id.is_none() && x.moves.is_empty()));
+    }
+
+    #[test]
+    fn v0_array_with_replays_is_migrated() {
+        let scores = round_trip(SCORES_V0_REPLAYS);
+
+        assert_eq!(scores.len(), 1);
+        assert_eq!(scores[0].seed, Some(42));
+        assert_eq!(scores[0].points, 412);
+        assert_eq!(scores[0].secret_number, Some(613));
+        assert_eq!(scores[0].moves.iter().map(|x| x.user_value).collect::<Vec<_>>(), [500, 750, 625, 613]);
+        assert_eq!(scores[0].player_id.as_deref(), Some("9cfe8007fb1f2237"));
+    }
+
+    #[test]
+    fn v1_envelope_is_migrated() {
+        let scores = round_trip(SCORES_V1);
+
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores[0].name, "Alicia");
+        assert_eq!(scores[0].hints_used, 1);
+        assert_eq!(scores[1].daily_challenge, chrono::NaiveDate::from_ymd_opt(2024, 6, 2));
+        assert_eq!(scores[1].first_guess_ms, Some(2_100));
+        assert!(scores.iter().all(|x| x.hot_seat_players.is_empty()));
+    }
+
+    #[test]
+    fn v2_hot_seat_score_is_loaded_unchanged() {
+        let scores = round_trip(SCORES_V2);
+
+        assert_eq!(scores.len(), 1);
+        assert_eq!(scores[0].tries, 5);
+        assert_eq!(scores[0].mode_label(), "3P");
+        assert_eq!(scores[0].hot_seat_players.iter().map(|x| x.name.as_str()).collect::<Vec<_>>(), ["ann", "bob", "cid"]);
+    }
+
+    #[test]
+    fn newer_version_is_rejected() {
+        let result = parse_score_file(r#"{"version": 99, "scores": []}"#);
+
+        assert!(matches!(result, Err(SchemaError::NewerVersion(99))));
+    }
+
+    #[test]
+    fn unknown_document_is_invalid() {
+        assert!(matches!(parse_score_file(r#"{"scores": []}"#), Err(SchemaError::Invalid(_))));
+        assert!(matches!(parse_score_file("42"), Err(SchemaError::Invalid(_))));
+    }
+}
@@ -0,0 +1,294 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use rusqlite::{params, Connection, ToSql};
+use crate::models::import_report::ImportReport;
+use crate::models::score::Score;
+use crate::profiles::normalize_name;
+use crate::scores::merge::merge_scores;
+use crate::scores::{ScoreQuery, ScoreStore, ScoreStoreError};
+
+pub const DATABASE_FILE_NAME: &str = "scores.db";
+
+// The whole score is kept as JSON in `data`, the other columns exist only to be filtered and sorted on.
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS scores (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL COLLATE NOCASE,
+        player_id TEXT,
+        tries INTEGER NOT NULL,
+        points INTEGER NOT NULL DEFAULT 0,
+        number_range TEXT NOT NULL,
+        is_hard_mode INTEGER NOT NULL,
+        completed_on TEXT NOT NULL,
+        daily_challenge TEXT,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS scores_range_mode_idx ON scores (number_range, is_hard_mode, tries);
+    CREATE INDEX IF NOT EXISTS scores_name_idx ON scores (name);
+    CREATE INDEX IF NOT EXISTS scores_completed_on_idx ON scores (completed_on);
+    CREATE INDEX IF NOT EXISTS scores_daily_challenge_idx ON scores (daily_challenge);
+";
+
+// Columns added after the first release, older databases get them filled from `data`.
+const ADD_PLAYER_ID: &str = "
+    BEGIN;
+    ALTER TABLE scores ADD COLUMN player_id TEXT;
+    UPDATE scores SET player_id = json_extract(data, '$.player_id');
+    COMMIT;
+";
+
+const ADD_POINTS: &str = "
+    BEGIN;
+    ALTER TABLE scores ADD COLUMN points INTEGER NOT NULL DEFAULT 0;
+    UPDATE scores SET points = json_extract(data, '$.points');
+    COMMIT;
+";
+
+const ADDED_COLUMN_INDEXES: &str = "
+    CREATE INDEX IF NOT EXISTS scores_player_id_idx ON scores (player_id);
+    CREATE INDEX IF NOT EXISTS scores_points_idx ON scores (points DESC, tries);
+";
+
+pub struct SqliteScoreStore {
+    connection: Connection,
+}
+
+impl SqliteScoreStore {
+    pub fn open(path: &Path) -> Result<SqliteScoreStore, ScoreStoreError> {
+        if let Some(parent) = path.parent().filter(|x| !x.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+
+        let connection = Connection::open(path)?;
+        connection.busy_timeout(std::time::Duration::from_secs(5))?;
+
+        SqliteScoreStore::from_connection(connection)
+    }
+
+    fn from_connection(connection: Connection) -> Result<SqliteScoreStore, ScoreStoreError> {
+        connection.execute_batch(SCHEMA)?;
+
+        for (column, migration) in [("player_id", ADD_PLAYER_ID), ("points", ADD_POINTS)] {
+            if !has_column(&connection, column)? {
+                connection.execute_batch(migration)?;
+            }
+        }
+        connection.execute_batch(ADDED_COLUMN_INDEXES)?;
+
+        Ok(SqliteScoreStore { connection })
+    }
+}
+
+fn has_column(connection: &Connection, column: &str) -> rusqlite::Result<bool> {
+    connection.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('scores') WHERE name = ?", [column], |row| row.get(0))
+}
+
+impl ScoreStore for SqliteScoreStore {
+    fn load_scores(&self, query: &ScoreQuery) -> Result<Vec<Score>, ScoreStoreError> {
+        let mut conditions = Vec::<&str>::new();
+        let mut values = Vec::<Box<dyn ToSql>>::new();
+
+        if let Some(number_range) = &query.number_range {
+            conditions.push("number_range = ?");
+            values.push(Box::new(number_range.clone()));
+        }
+        if let Some(is_hard_mode) = query.is_hard_mode {
+            conditions.push("is_hard_mode = ?");
+            values.push(Box::new(is_hard_mode));
+        }
+        match (&query.player, &query.player_id) {
+            (Some(player), Some(player_id)) => {
+                conditions.push("(player_id = ? OR (player_id IS NULL AND name = ?))");
+                values.push(Box::new(player_id.clone()));
+                values.push(Box::new(normalize_name(player)));
+            }
+            (Some(player), None) => {
+                conditions.push("name = ?");
+                values.push(Box::new(normalize_name(player)));
+            }
+            _ => {}
+        }
+        if let Some(date) = query.daily_challenge {
+            conditions.push("daily_challenge = ?");
+            values.push(Box::new(date.to_string()));
+        }
+        if let Some(date) = query.completed_on {
+            conditions.push("completed_on = ?");
+            values.push(Box::new(date.to_string()));
+        }
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+
+        // A negative limit means no limit.
+        values.push(Box::new(query.limit.map_or(-1, |x| x as i64)));
+        values.push(Box::new(query.offset as i64));
+
+        let mut statement = self.connection.prepare(&format!(
+            "SELECT data FROM scores {where_clause} ORDER BY points DESC, tries, id LIMIT ? OFFSET ?"))?;
+        let rows = statement.query_map(
+            rusqlite::params_from_iter(values.iter().map(|x| x.as_ref())),
+            |row| row.get::<_, String>(0))?;
+
+        let mut scores = Vec::new();
+        for data in rows {
+            scores.push(serde_json::from_str(&data?).map_err(ScoreStoreError::Serialize)?);
+        }
+
+        Ok(scores)
+    }
+
+    fn add_score(&self, score: Score) -> Result<Option<PathBuf>, ScoreStoreError> {
+        insert_score(&self.connection, &score)?;
+
+        Ok(None)
+    }
+
+    fn clear_scores(&self) -> Result<(), ScoreStoreError> {
+        self.connection.execute("DELETE FROM scores", [])?;
+
+        Ok(())
+    }
+
+    fn import_scores(&self, scores: Vec<Score>) -> Result<ImportReport, ScoreStoreError> {
+        let transaction = self.connection.unchecked_transaction()?;
+        let existing = self.load_scores(&ScoreQuery::default())?;
+        let (new_scores, report) = merge_scores(&existing, scores);
+
+        for score in &new_scores {
+            insert_score(&transaction, score)?;
+        }
+
+        transaction.commit()?;
+        Ok(report)
+    }
+}
+
+fn insert_score(connection: &Connection, score: &Score) -> Result<(), ScoreStoreError> {
+    let data = serde_json::to_string(score).map_err(ScoreStoreError::Serialize)?;
+
+    connection.execute(
+        "INSERT INTO scores (name, player_id, tries, points, number_range, is_hard_mode, completed_on, daily_challenge, data)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            score.name,
+            score.player_id,
+            score.tries,
+            score.points,
+            score.number_range,
+            score.is_hard_mode,
+            score.completed_at.date_naive().to_string(),
+            score.daily_challenge.map(|x| x.to_string()),
+            data,
+        ])?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Local, NaiveDate, TimeZone};
+    use super::*;
+    use crate::game::Game;
+    use crate::models::game_rules::GameRules;
+    use crate::models::profile::Profile;
+    use crate::scores::create_score;
+
+    fn open_in_memory() -> SqliteScoreStore {
+        SqliteScoreStore::from_connection(Connection::open_in_memory().unwrap()).unwrap()
+    }
+
+    fn score(name: &str, player_id: Option<&str>, points: u32, number_range: &str, day: u32) -> Score {
+        let mut game = Game::from_seed(1..=100, GameRules::default(), 42);
+        game.guess(game.info.generated_number);
+
+        let player = Profile { id: String::new(), name: name.to_string(), created_at: Local::now() };
+        let completed_at = Local.with_ymd_and_hms(2024, 6, day, 12, 0, 0).unwrap();
+        let mut score = create_score(&player, &game);
+
+        score.player_id = player_id.map(str::to_string);
+        score.points = points;
+        score.number_range = number_range.to_string();
+        score.started_at = completed_at - chrono::Duration::seconds(10);
+        score.completed_at = completed_at;
+        score
+    }
+
+    fn names(scores: &[Score]) -> Vec<&str> {
+        scores.iter().map(|x| x.name.as_str()).collect()
+    }
+
+    #[test]
+    fn scores_are_filtered_sorted_by_points_and_paged() {
+        let store = open_in_memory();
+        store.add_score(score("ann", Some("a"), 500, "1-100", 1)).unwrap();
+        store.add_score(score("bob", Some("b"), 900, "1-100", 2)).unwrap();
+        store.add_score(score("cid", None, 700, "1-1000", 2)).unwrap();
+        store.add_score(score("Ann", None, 800, "1-100", 3)).unwrap();
+
+        let cases = [
+            (ScoreQuery::default(), vec!["bob", "Ann", "cid", "ann"]),
+            (ScoreQuery { offset: 1, limit: Some(2), ..ScoreQuery::default() }, vec!["Ann", "cid"]),
+            (ScoreQuery { number_range: Some("1-1000".to_string()), ..ScoreQuery::default() }, vec!["cid"]),
+            (ScoreQuery { player: Some(" ANN ".to_string()), ..ScoreQuery::default() }, vec!["Ann", "ann"]),
+            // Scores with another player id do not match the name, scores without one do.
+            (ScoreQuery { player: Some("ann".to_string()), player_id: Some("x".to_string()), ..ScoreQuery::default() },
+             vec!["Ann"]),
+            (ScoreQuery { completed_on: NaiveDate::from_ymd_opt(2024, 6, 2), ..ScoreQuery::default() }, vec!["bob", "cid"]),
+            (ScoreQuery { is_hard_mode: Some(true), ..ScoreQuery::default() }, vec![]),
+        ];
+
+        for (query, expected) in cases {
+            assert_eq!(names(&store.load_scores(&query).unwrap()), expected);
+        }
+    }
+
+    #[test]
+    fn import_adds_only_new_scores() {
+        let store = open_in_memory();
+        store.add_score(score("ann", Some("a"), 500, "1-100", 1)).unwrap();
+
+        let mut conflict = score("ann", Some("a"), 500, "1-100", 1);
+        conflict.tries += 1;
+        let imported = vec![score("ann", Some("a"), 500, "1-100", 1), conflict, score("bob", None, 600, "1-100", 2)];
+        let report = store.import_scores(imported).unwrap();
+
+        assert_eq!((report.added, report.duplicates, report.conflicts.len()), (1, 1, 1));
+        assert_eq!(names(&store.load_scores(&ScoreQuery::default()).unwrap()), vec!["bob", "ann"]);
+    }
+
+    #[test]
+    fn database_without_added_columns_is_migrated() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection.execute_batch("
+            CREATE TABLE scores (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL COLLATE NOCASE,
+                tries INTEGER NOT NULL,
+                number_range TEXT NOT NULL,
+                is_hard_mode INTEGER NOT NULL,
+                completed_on TEXT NOT NULL,
+                daily_challenge TEXT,
+                data TEXT NOT NULL
+            );").unwrap();
+
+        for score in [score("ann", Some("a"), 500, "1-100", 1), score("bob", None, 900, "1-100", 2)] {
+            connection.execute(
+                "INSERT INTO scores (name, tries, number_range, is_hard_mode, completed_on, data)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![score.name, score.tries, score.number_range, score.is_hard_mode,
+                        score.completed_at.date_naive().to_string(), serde_json::to_string(&score).unwrap()]).unwrap();
+        }
+
+        let store = SqliteScoreStore::from_connection(connection).unwrap();
+        let by_player_id = ScoreQuery { player: Some("renamed".to_string()), player_id: Some("a".to_string()),
+                                        ..ScoreQuery::default() };
+
+        assert_eq!(names(&store.load_scores(&ScoreQuery::default()).unwrap()), vec!["bob", "ann"]);
+        assert_eq!(names(&store.load_scores(&by_player_id).unwrap()), vec!["ann"]);
+    }
+}
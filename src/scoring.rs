@@ -0,0 +1,109 @@
+//! Points make games on different ranges comparable.
+//!
+//! A game is worth `100` points per bit of information needed to find the number
+//! (`ceil(log2(range size))`, which is also what binary search needs). That base is scaled by
+//! how close the player was to that optimum, how fast the game was and whether it was hard mode.
+//! Every hint taken during the game costs a share of the points.
+
+use crate::models::score::Score;
+
+const POINTS_PER_BIT: f64 = 100.0;
+const MAX_EFFICIENCY: f64 = 1.5;
+const SECONDS_PER_GUESS: f64 = 10.0;
+const HARD_MODE_BONUS: f64 = 1.25;
+const HINT_PENALTY: f64 = 0.1;
+
+pub fn parse_number_range(number_range: &str) -> Option<(i64, i64)> {
+    let (min, max) = number_range.split_once('-')?;
+
+    Some((min.trim().parse().ok()?, max.trim().parse().ok()?))
+}
+
+/// Orders ranges by their bounds, ranges that can not be parsed go last.
+pub fn range_sort_key(number_range: &str) -> (i64, i64) {
+    parse_number_range(number_range).unwrap_or((i64::MAX, i64::MAX))
+}
+
+pub fn optimal_tries(min_number: i64, max_number: i64) -> u32 {
+    let range_size = (max_number - min_number + 1).max(1) as u64;
+
+    range_size.next_power_of_two().trailing_zeros().max(1)
+}
+
+pub fn compute_points(score: &Score) -> u32 {
+    let Some((min_number, max_number)) = parse_number_range(&score.number_range) else {
+        return 0;
+    };
+
+    let optimum = optimal_tries(min_number, max_number) as f64;
+    let efficiency = (optimum / score.tries.max(1) as f64).min(MAX_EFFICIENCY);
+
+    let seconds = score.completed_for_ms.max(0) as f64 / 1000.0;
+    let time_factor = 0.5 + 0.5 / (1.0 + seconds / (optimum * SECONDS_PER_GUESS));
+
+    let mode_factor = if score.is_hard_mode { HARD_MODE_BONUS } else { 1.0 };
+    let hint_factor = (1.0 - HINT_PENALTY * score.hints_used as f64).max(0.0);
+
+    (POINTS_PER_BIT * optimum * efficiency * time_factor * mode_factor * hint_factor).round() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Local;
+    use super::*;
+
+    fn score(number_range: &str, tries: i32, completed_for_ms: i64, is_hard_mode: bool, hints_used: u32) -> Score {
+        Score {
+            name: "alice".to_string(),
+            player_id: None,
+            tries,
+            started_at: Local::now(),
+            completed_at: Local::now(),
+            completed_for_ms,
+            number_range: number_range.to_string(),
+            is_hard_mode,
+            seed: None,
+            daily_challenge: None,
+            points: 0,
+            first_guess_ms: None,
+            slowest_guess_ms: None,
+            secret_number: None,
+            moves: Vec::new(),
+            hints_used,
+            hot_seat_players: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn optimal_tries_is_what_binary_search_needs() {
+        let cases = [((1, 1), 1), ((1, 2), 1), ((1, 100), 7), ((1, 128), 7), ((1, 129), 8), ((1, 1000), 10)];
+
+        for ((min_number, max_number), expected) in cases {
+            assert_eq!(optimal_tries(min_number, max_number), expected, "{min_number}-{max_number}");
+        }
+    }
+
+    #[test]
+    fn points_reward_efficiency_speed_and_hard_mode() {
+        // 1-100 needs 7 tries, so an optimal instant game is worth 700 points.
+        let cases = [
+            (score("1-100", 7, 0, false, 0), 700),
+            (score("1-100", 14, 0, false, 0), 350),
+            // Lucky games are capped at 1.5 times the optimum.
+            (score("1-100", 4, 0, false, 0), 1050),
+            (score("1-100", 1, 0, false, 0), 1050),
+            // 70 seconds is 10 seconds per optimal guess, which halves the time bonus.
+            (score("1-100", 7, 70_000, false, 0), 525),
+            (score("1-100", 7, 0, true, 0), 875),
+            (score("1-100", 7, 0, false, 2), 560),
+            (score("1-100", 7, 0, false, 11), 0),
+            (score("1-1000", 10, 0, false, 0), 1000),
+            (score("not a range", 7, 0, false, 0), 0),
+        ];
+
+        for (score, expected) in cases {
+            assert_eq!(compute_points(&score), expected,
+                       "{} in {} tries, {} ms, {} hints", score.number_range, score.tries, score.completed_for_ms, score.hints_used);
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! Binary search over the hints a game has given so far.
+//!
+//! Every "higher" or "lower" hint cuts the range of numbers that can still be the answer, the
+//! optimal next guess is the middle of what is left.
+
+use std::ops::RangeInclusive;
+use crate::game::{compare_guess, GuessOutcome};
+
+pub fn feasible_interval(range: RangeInclusive<i32>, hints: &[(i32, GuessOutcome)]) -> RangeInclusive<i32> {
+    let (mut low, mut high) = range.into_inner();
+
+    for (value, outcome) in hints {
+        match outcome {
+            GuessOutcome::TooLow => low = low.max(value.saturating_add(1)),
+            GuessOutcome::TooHigh => high = high.min(value.saturating_sub(1)),
+            GuessOutcome::Correct => {
+                low = *value;
+                high = *value;
+            }
+            GuessOutcome::Invalid(_) | GuessOutcome::Lost => {}
+        }
+    }
+
+    low..=high
+}
+
+pub fn optimal_guess(interval: &RangeInclusive<i32>) -> i32 {
+    let (low, high) = (*interval.start() as i64, *interval.end() as i64);
+
+    ((low + high) / 2) as i32
+}
+
+/// Number of tries binary search needs to find `secret_number` in `range`.
+pub fn optimal_tries_for(range: RangeInclusive<i32>, secret_number: i32) -> u32 {
+    let mut hints = Vec::new();
+
+    loop {
+        let interval = feasible_interval(range.clone(), &hints);
+        let guess = optimal_guess(&interval);
+        let outcome = compare_guess(guess, secret_number);
+        let is_found = matches!(outcome, GuessOutcome::Correct) || interval.is_empty();
+
+        hints.push((guess, outcome));
+
+        if is_found {
+            return hints.len() as u32;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Game;
+    use crate::models::game_rules::GameRules;
+
+    #[test]
+    fn hints_narrow_the_interval() {
+        let hints = [(50, GuessOutcome::TooLow), (80, GuessOutcome::TooHigh), (60, GuessOutcome::TooLow)];
+
+        assert_eq!(feasible_interval(1..=100, &[]), 1..=100);
+        assert_eq!(feasible_interval(1..=100, &hints), 61..=79);
+        assert_eq!(feasible_interval(1..=100, &[(42, GuessOutcome::Correct)]), 42..=42);
+    }
+
+    #[test]
+    fn optimal_guess_does_not_overflow() {
+        assert_eq!(optimal_guess(&(1..=100)), 50);
+        assert_eq!(optimal_guess(&(i32::MAX - 2..=i32::MAX)), i32::MAX - 1);
+    }
+
+    #[test]
+    fn binary_search_needs_at_most_log2_tries() {
+        let worst_case = |max: i32| (1..=max).map(|secret| optimal_tries_for(1..=max, secret)).max();
+
+        assert_eq!(worst_case(100), Some(7));
+        assert_eq!(worst_case(1000), Some(10));
+        assert_eq!(optimal_tries_for(1..=100, 50), 1);
+    }
+
+    #[test]
+    fn following_the_hints_wins_in_optimal_tries() {
+        for seed in 0..20 {
+            let mut game = Game::from_seed(1..=1000, GameRules::default(), seed);
+
+            while !game.info.is_game_over {
+                let guess = game.use_hint();
+                game.guess(guess);
+            }
+
+            assert!(!game.info.is_game_lost);
+            assert_eq!(game.tries() as u32, game.optimal_tries(), "seed {seed}");
+            assert_eq!(game.info.hints_used, game.optimal_tries());
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use std::io;
+use std::panic;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use crossterm::{
+    cursor::Show,
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+/// Keeps the terminal in raw mode with the alternate screen while alive, restores it when dropped.
+pub struct TerminalGuard;
+
+impl TerminalGuard {
+    pub fn enter() -> io::Result<TerminalGuard> {
+        install_panic_hook();
+
+        enable_raw_mode()?;
+        let guard = TerminalGuard;
+        execute!(io::stderr(), EnterAlternateScreen, EnableMouseCapture)?;
+
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+pub fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stderr(), LeaveAlternateScreen, DisableMouseCapture, Show);
+}
+
+// The default hook prints the panic message, so the terminal has to be restored before it runs.
+fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        default_hook(info);
+    }));
+}
+
+/// Returns a flag that is set once the process is asked to stop (SIGTERM, SIGHUP, SIGINT, SIGQUIT).
+pub fn register_exit_signals() -> io::Result<Arc<AtomicBool>> {
+    let exit_requested = Arc::new(AtomicBool::new(false));
+
+    #[cfg(unix)]
+    for signal in signal_hook::consts::TERM_SIGNALS.iter().chain([&signal_hook::consts::SIGHUP]) {
+        signal_hook::flag::register(*signal, Arc::clone(&exit_requested))?;
+    }
+
+    Ok(exit_requested)
+}
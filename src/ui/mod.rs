@@ -1,4 +1,9 @@
 pub mod ui_footer;
 pub mod ui_header;
 pub mod ui_leaderboard;
-pub mod ui_helpers;
\ No newline at end of file
+pub mod ui_helpers;
+pub mod ui_custom_game;
+pub mod ui_replay;
+pub mod ui_profiles;
+pub mod ui_race;
+pub mod ui_hot_seat;
@@ -0,0 +1,52 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use crate::app::App;
+use crate::models::custom_game_field::CustomGameField;
+
+pub fn render_custom_game(f: &mut Frame, area: Rect, app: &App) {
+    let info = &app.custom_game_info;
+
+    let lines = vec![
+        create_field_line("Min number", info.min_input.as_str(),
+                          info.selected_field == CustomGameField::MinNumber),
+        create_field_line("Max number", info.max_input.as_str(),
+                          info.selected_field == CustomGameField::MaxNumber),
+        create_field_line("Max guesses", create_limit_value(info.max_guesses_input.as_str()),
+                          info.selected_field == CustomGameField::MaxGuesses),
+        create_field_line("Time limit", create_limit_value(info.time_limit_input.as_str()),
+                          info.selected_field == CustomGameField::TimeLimit),
+        create_field_line("Hard mode", if info.is_hard_mode { "[x]" } else { "[ ]" },
+                          info.selected_field == CustomGameField::HardMode),
+        create_field_line("Invalid = try", if info.is_invalid_guess_counted { "[x]" } else { "[ ]" },
+                          info.selected_field == CustomGameField::CountInvalid),
+        Line::from(""),
+        create_field_line("Start game", "",
+                          info.selected_field == CustomGameField::Start),
+    ];
+
+    f.render_widget(Paragraph::new(lines)
+                        .block(Block::default().borders(Borders::ALL)), area);
+}
+
+fn create_limit_value(value: &str) -> &str {
+    if value.is_empty() { "unlimited" } else { value }
+}
+
+fn create_field_line<'a>(title: &str, value: &str, is_selected: bool) -> Line<'a> {
+    let style = if is_selected {
+        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::White)
+    };
+
+    Line::from(vec![
+        Span::styled(if is_selected { ">> " } else { "   " }, style),
+        Span::styled(format!("{title:<14}"), style),
+        Span::styled(value.to_owned(), Style::default().fg(Color::LightGreen)),
+    ])
+}
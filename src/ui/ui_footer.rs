@@ -4,21 +4,22 @@ use ratatui::{
     text::{Span, Line},
 };
 use crate::app::{App};
+use crate::daily_challenge::today;
+use crate::formatting::format_duration_ms;
 use crate::models::current_screen::CurrentScreen;
 
 pub fn create_footer_navigation<'a>(text: &str) -> Paragraph<'a> {
     let footer_hotkeys_data = Span::styled(text.to_owned(), 
        Style::default().fg(Color::LightRed)).into_centered_line();
 
-    Paragraph::new(
-        Line::from(footer_hotkeys_data))
+    Paragraph::new(footer_hotkeys_data)
         .block(Block::default().borders(Borders::ALL))
 }
 
 pub fn create_footer_left_part<'a>(app: &App) -> Paragraph<'a> {
     let footer_text_data = match app.current_screen {
         CurrentScreen::Game => {
-            let guesses_made: i32 = app.user_input_history.len() as i32;
+            let guesses_made: i32 = app.game.tries() as i32;
             let guess_color = match guesses_made {
                 0..=3 => Color::LightGreen,
                 4..=7 => Color::Yellow,
@@ -27,25 +28,87 @@ pub fn create_footer_left_part<'a>(app: &App) -> Paragraph<'a> {
             };
             let span_style = Style::default().fg(guess_color);
 
-            vec![
+            let mut spans = vec![
                 Span::styled("guesses made", span_style),
                 Span::styled(" : ", span_style),
                 Span::styled(guesses_made.to_string(), span_style),
-            ]},
+            ];
+
+            if let Some(guesses_left) = app.game.remaining_guesses() {
+                spans.push(Span::styled(format!(" ({guesses_left} left)"), span_style));
+            }
+
+            spans.push(Span::from(" | ").dark_gray());
+            spans.push(Span::from(format!("time : {}", format_duration_ms(app.game.elapsed().num_milliseconds()))).gray());
+
+            if let Some(time_left) = app.game.remaining_time() {
+                let time_left = time_left.num_milliseconds().max(0);
+                let time_color = if time_left <= 10_000 { Color::LightRed } else { Color::LightGreen };
+
+                spans.push(Span::styled(format!(" ({} left)", format_duration_ms(time_left)),
+                                        Style::default().fg(time_color)));
+            }
+
+            if app.game.info.hints_used > 0 {
+                spans.push(Span::from(" | ").dark_gray());
+                spans.push(Span::from(format!("hints : {}", app.game.info.hints_used)).yellow());
+            }
+
+            spans
+        },
         CurrentScreen::Leaderboard => {
-            vec![Span::from("Top 15 shown").light_green()]
+            let leaderboard = &app.leaderboard;
+            let position = match leaderboard.table_state.selected() {
+                Some(selected) => format!("{}/{}", selected + 1, leaderboard.scores.len()),
+                None => "0/0".to_string(),
+            };
+
+            let mut spans = vec![
+                Span::from(leaderboard.range_filter.as_deref().unwrap_or("all ranges").to_owned()).light_green(),
+                Span::from(" | ").dark_gray(),
+                Span::from(leaderboard.mode_filter.title().to_owned()).light_green(),
+            ];
+
+            if leaderboard.is_daily_only {
+                spans.push(Span::from(" | ").dark_gray());
+                spans.push(Span::from(format!("daily {}", today().format("%m-%d"))).light_green());
+            }
+
+            spans.push(Span::from(" | ").dark_gray());
+            spans.push(Span::from(format!("by {}", leaderboard.sort_key.title())).light_green());
+            spans.push(Span::from(" | ").dark_gray());
+            spans.push(Span::from(position).dark_gray());
+
+            spans
+        },
+        CurrentScreen::Replay => match &app.replay {
+            Some(replay) => {
+                let score = &replay.score;
+                let position_ms = match replay.step {
+                    0 => 0,
+                    step => (score.moves[step - 1].move_done_at - score.started_at).num_milliseconds(),
+                };
+
+                vec![
+                    Span::from(format!("step : {}/{}", replay.step, score.moves.len())).light_green(),
+                    Span::from(" | ").dark_gray(),
+                    Span::from(format!("time : {}/{}", format_duration_ms(position_ms),
+                                       format_duration_ms(score.completed_for_ms))).gray(),
+                    Span::from(" | ").dark_gray(),
+                    Span::from(if replay.is_playing { "playing" } else { "paused" }).yellow(),
+                ]
+            }
+            None => vec![],
         },
         _ => {vec![]}
     };
 
-    let footer_text = if footer_text_data.len() > 0 {
+    if !footer_text_data.is_empty() {
         Paragraph::new(
             Line::from(footer_text_data))
             .block(Block::default().borders(Borders::ALL))
             .centered()
     } else {
         Paragraph::new(Line::from(""))
-    };
-
-    return footer_text;
+    }
 }
\ No newline at end of file
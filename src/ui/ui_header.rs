@@ -1,11 +1,18 @@
 use ratatui:: {
   widgets::{Block, Borders, Paragraph},
   style::Stylize,
-  text::Text,
+  text::{Line, Text},
 };
 
 pub fn create_header<'a>(title_text: &str) -> Paragraph<'a> {
     Paragraph::new(Text::from(title_text.to_owned()).green())
         .block(Block::default().borders(Borders::ALL))
         .centered()
-}
\ No newline at end of file
+}
+
+pub fn create_error_banner<'a>(error_text: &str) -> Paragraph<'a> {
+    Paragraph::new(Line::from(error_text.to_owned()))
+        .white()
+        .on_red()
+        .centered()
+}
@@ -1,4 +1,5 @@
 use ratatui::layout::{Constraint, Layout, Rect};
+use crate::formatting::format_duration_ms;
 use crate::models::score::Score;
 
 pub fn constraint_len_calculator(score: &[Score]) -> (u16, u16, u16, u16, u16) {
@@ -22,7 +23,7 @@ pub fn constraint_len_calculator(score: &[Score]) -> (u16, u16, u16, u16, u16) {
 
     let completed_for_msec_len = score
         .iter()
-        .map(|q| { q.completed_for_ms.to_string().len()})
+        .map(|q| { format_duration_ms(q.completed_for_ms).len()})
         .max()
         .unwrap_or(0);
 
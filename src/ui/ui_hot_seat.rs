@@ -0,0 +1,44 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem},
+    Frame,
+};
+use crate::game::Game;
+use crate::models::hot_seat_setup_info::HotSeatSetupInfo;
+
+pub fn render_hot_seat_setup(f: &mut Frame, area: Rect, setup: &HotSeatSetupInfo) {
+    let items: Vec<ListItem> = setup.players.iter().enumerate().map(|(i, name)| {
+        ListItem::new(Line::from(vec![
+            Span::from(format!("{:>2}. ", i + 1)).dark_gray(),
+            Span::from(name.to_owned()).white(),
+        ]))
+    }).collect();
+
+    f.render_widget(List::new(items)
+                        .block(Block::default().borders(Borders::ALL).title(" Players ")), area);
+}
+
+pub fn render_hot_seat_players(f: &mut Frame, area: Rect, game: &Game) {
+    let current_player = game.current_player().filter(|_| !game.info.is_game_over);
+    let winner = game.winner();
+
+    let items: Vec<ListItem> = game.player_tries().into_iter().map(|player| {
+        let name = Span::from(format!("{:<16}", player.name));
+        let tries = Span::from(format!("{} guesses", player.tries)).gray();
+
+        let spans = if current_player == Some(player.name.as_str()) {
+            vec![Span::from(">> ").light_green(), name.light_green().add_modifier(Modifier::BOLD), tries]
+        } else if winner == Some(player.name.as_str()) {
+            vec![Span::from("   "), name.light_green(), tries, Span::from("  won").light_green()]
+        } else {
+            vec![Span::from("   "), name.white(), tries]
+        };
+
+        ListItem::new(Line::from(spans))
+    }).collect();
+
+    f.render_widget(List::new(items)
+                        .block(Block::default().borders(Borders::ALL).title(" Players ")), area);
+}
@@ -1,26 +1,27 @@
 use ratatui:: {
     layout::{Constraint, Rect},
-    widgets::{Cell, Row, Table, HighlightSpacing},
-    style::{Style, palette::tailwind, Stylize},
+    widgets::{Cell, Row, Table, TableState, HighlightSpacing},
+    style::{Modifier, Style, palette::tailwind, Stylize},
     text::{Text},
     Frame,
 };
+use crate::formatting::format_duration_ms;
 use crate::models::score::Score;
 use crate::ui::ui_helpers::constraint_len_calculator;
 
-pub fn render_leaderboard_table(f: &mut Frame, area: Rect, scores: &Vec<Score>) {
+pub fn render_leaderboard_table(f: &mut Frame, area: Rect, scores: &[Score], table_state: &mut TableState) {
     let header_style = Style::default()
         .fg(tailwind::SLATE.c200)
         .bg(tailwind::BLUE.c900);
 
-    let header = ["#", "Name", "Tries", "Game range", "Mode", "Game time"]
+    let header = ["#", "Name", "Points", "Tries", "Game range", "Mode", "Game time"]
         .into_iter()
         .map(Cell::from)
         .collect::<Row>()
         .style(header_style)
         .height(1);
 
-    let rows = scores.iter().enumerate().take(15).map(|(i, data)| {
+    let rows = scores.iter().enumerate().map(|(i, data)| {
         let color = match i % 2 {
             0 => tailwind::SLATE.c950,
             _ => tailwind::SLATE.c900,
@@ -29,45 +30,45 @@ pub fn render_leaderboard_table(f: &mut Frame, area: Rect, scores: &Vec<Score>)
         Row::new(vec![
             Cell::from(Text::from(format!("{}", i + 1)))
                 .style(Style::new().fg(tailwind::SLATE.c600).bg(color)),
-            Cell::from(Text::from(format!("{}", data.name)))
+            Cell::from(Text::from(data.name.to_string()))
                 .style(Style::new().fg(tailwind::SLATE.c200).bg(color)),
+            Cell::from(Text::from(format!("{}", data.points))
+                .centered())
+                .style(Style::new().fg(tailwind::AMBER.c300).bg(color)),
             Cell::from(Text::from(format!("{}", data.tries))
                 .centered())
                 .style(Style::new().fg(tailwind::GREEN.c300).bg(color)),
-            Cell::from(Text::from(format!("{}", data.number_range))
+            Cell::from(Text::from(data.number_range.to_string())
                 .centered())
                 .style(Style::new().fg(tailwind::SLATE.c200).bg(color)),
-            Cell::from(Text::from(format!("{}", if data.is_hard_mode {"H"} else {""}))
+            Cell::from(Text::from(data.mode_label())
                 .centered())
                 .style(Style::new().fg(tailwind::SLATE.c200).bg(color)),
-            Cell::from(Text::from(format!("{}ms", data.completed_for_ms))
+            Cell::from(Text::from(format_duration_ms(data.completed_for_ms))
                 .centered())
                 .style(Style::new().fg(tailwind::SLATE.c600).bg(color)),
         ])
     });
 
     let bar = " █ ";
-    let longest_score_item_len = constraint_len_calculator(&scores);
+    let longest_score_item_len = constraint_len_calculator(scores);
 
     let table = Table::new(rows,
                            [
                                // Constraint::Length(longest_score_item_len.0 + 1),
-                               Constraint::Length(2),
+                               Constraint::Length(scores.len().to_string().len() as u16 + 1),
                                Constraint::Min(longest_score_item_len.0 + 1),
+                               Constraint::Min(7),
                                Constraint::Min(longest_score_item_len.1 + 1),
-                               Constraint::Min(longest_score_item_len.1 + 2),
+                               Constraint::Min(longest_score_item_len.2 + 2),
                                Constraint::Min(longest_score_item_len.1 + 3),
                                Constraint::Min(longest_score_item_len.4),
                            ],
     )
         .header(header)
-        .highlight_symbol(Text::from(vec![
-            "".into(),
-            bar.into(),
-            bar.into(),
-            "".into(),
-        ]))
+        .highlight_symbol(Text::from(bar))
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
         .bg(tailwind::SLATE.c950)
         .highlight_spacing(HighlightSpacing::Always);
-    f.render_widget(table, area);
+    f.render_stateful_widget(table, area, table_state);
 }
\ No newline at end of file
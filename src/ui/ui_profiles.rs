@@ -0,0 +1,37 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem},
+    Frame,
+};
+use crate::app::App;
+
+pub fn render_profiles(f: &mut Frame, area: Rect, app: &mut App) {
+    let active_id = app.profiles.active_player_id.as_deref();
+
+    let items: Vec<ListItem> = app.profiles.profiles.iter().map(|profile| {
+        let mut spans = vec![Span::from(profile.name.clone()).white()];
+
+        if active_id == Some(profile.id.as_str()) {
+            spans.push(Span::from("  (active)").dark_gray());
+        }
+
+        ListItem::new(Line::from(spans))
+    }).collect();
+
+    let block = Block::default().borders(Borders::ALL);
+
+    if items.is_empty() {
+        f.render_widget(List::new([ListItem::new("No players yet, press (n) to add one").dark_gray()])
+                            .block(block), area);
+        return;
+    }
+
+    f.render_stateful_widget({
+        List::new(items)
+            .block(block)
+            .highlight_style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
+            .highlight_symbol(">> ")
+    }, area, &mut app.profiles_info.list_state);
+}
@@ -0,0 +1,47 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem},
+    Frame,
+};
+use crate::models::race_info::RaceInfo;
+use crate::models::race_player_state::RacePlayerState;
+
+pub fn render_race_lobby(f: &mut Frame, area: Rect, race: &RaceInfo) {
+    let items: Vec<ListItem> = race.players.iter().map(|player| {
+        ListItem::new(create_player_name(&player.name, race))
+    }).collect();
+
+    let title = format!(" Players {}/{} ", race.players.len(), race.expected_players);
+
+    f.render_widget(List::new(items)
+                        .block(Block::default().borders(Borders::ALL).title(title)), area);
+}
+
+pub fn render_race_standings(f: &mut Frame, area: Rect, race: &RaceInfo) {
+    let items: Vec<ListItem> = race.players.iter().map(|player| {
+        let state = Span::from(format!("  {}", player.state.title()));
+
+        ListItem::new(Line::from(vec![
+            create_player_name(&player.name, race),
+            Span::from(format!("  {} guesses", player.tries)).gray(),
+            match player.state {
+                RacePlayerState::Playing => state.dark_gray(),
+                RacePlayerState::Won => state.light_green(),
+                RacePlayerState::Lost => state.light_red(),
+            },
+        ]))
+    }).collect();
+
+    f.render_widget(List::new(items)
+                        .block(Block::default().borders(Borders::ALL).title(" Race ")), area);
+}
+
+fn create_player_name<'a>(name: &str, race: &RaceInfo) -> Span<'a> {
+    if name == race.name {
+        Span::from(format!("{name} (you)")).light_green().add_modifier(Modifier::BOLD)
+    } else {
+        Span::from(name.to_owned()).white()
+    }
+}
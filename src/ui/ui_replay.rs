@@ -0,0 +1,56 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+use crate::formatting::format_duration_ms;
+use crate::game::{compare_guess, move_durations, GuessOutcome};
+use crate::models::replay_info::ReplayInfo;
+
+pub fn render_replay(f: &mut Frame, area: Rect, replay: &ReplayInfo) {
+    let score = &replay.score;
+
+    let Some(secret_number) = score.secret_number.filter(|_| !score.moves.is_empty()) else {
+        f.render_widget(Paragraph::new(Line::from("No replay was recorded for this game"))
+                            .style(Style::default().fg(Color::DarkGray))
+                            .block(Block::default().borders(Borders::ALL)), area);
+        return;
+    };
+
+    let durations = move_durations(score.started_at, &score.moves);
+    let mut list_items = Vec::<ListItem>::new();
+
+    for (i, (item, duration)) in score.moves.iter().zip(durations).take(replay.step).enumerate() {
+        let (hint, color) = match compare_guess(item.user_value, secret_number) {
+            GuessOutcome::TooLow => (format!("Number is > than {}", item.user_value), Color::Yellow),
+            GuessOutcome::TooHigh => (format!("Number is < than {}", item.user_value), Color::Yellow),
+            _ => ("Correct!".to_string(), Color::LightGreen),
+        };
+
+        let mut spans = vec![Span::styled(format!("{:>3}. ", i + 1), Style::default().fg(Color::DarkGray))];
+
+        // Hot-seat players are stored in turn order.
+        if !score.hot_seat_players.is_empty() {
+            let player = &score.hot_seat_players[i % score.hot_seat_players.len()];
+            spans.push(Span::styled(format!("{:<16}", player.name), Style::default().fg(Color::Gray)));
+        }
+
+        spans.extend([
+            Span::styled(format!("{:<12}", item.user_value), Style::default().fg(Color::White)),
+            Span::styled(format!("{hint:<28}"), Style::default().fg(color)),
+            Span::styled(
+                format!("+{}", format_duration_ms(duration.num_milliseconds())),
+                Style::default().fg(Color::DarkGray).add_modifier(Modifier::DIM),
+            ),
+        ]);
+
+        list_items.push(ListItem::new(Line::from(spans)));
+    }
+
+    list_items.reverse();
+
+    f.render_widget(List::new(list_items)
+                        .block(Block::default().borders(Borders::ALL)), area);
+}
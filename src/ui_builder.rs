@@ -7,22 +7,33 @@ use ratatui::{
 };
 
 use crate::app::{App};
+use crate::formatting::format_duration_ms;
+use crate::game::GuessOutcome;
 use crate::models::current_screen::CurrentScreen;
-use crate::scores::load_scores;
+use crate::models::profile_edit::ProfileEdit;
+use crate::models::user_input_mode::UserInputMode;
+use crate::ui::ui_custom_game::render_custom_game;
 use crate::ui::ui_footer::{create_footer_left_part, create_footer_navigation};
-use crate::ui::ui_header::create_header;
+use crate::ui::ui_header::{create_error_banner, create_header};
 use crate::ui::ui_helpers::centered_rect;
+use crate::ui::ui_hot_seat::{render_hot_seat_players, render_hot_seat_setup};
 use crate::ui::ui_leaderboard::render_leaderboard_table;
+use crate::ui::ui_profiles::render_profiles;
+use crate::ui::ui_race::{render_race_lobby, render_race_standings};
+use crate::ui::ui_replay::render_replay;
 
 const INFO_TEXT: &str = "(↑) move up | (↓) move down | (Enter) select";
 
 pub fn ui_builder(f: &mut Frame, app: &mut App) {
-    let rects = Layout::vertical([
+    let banner_height = if app.error_banner.is_some() { 1 } else { 0 };
+    let screen_rects = Layout::vertical([
+        Constraint::Length(banner_height),
         Constraint::Length(3),
         Constraint::Max(1),
         Constraint::Min(1),
         Constraint::Length(3),
     ]).split(f.size());
+    let rects = &screen_rects[1..];
 
     match app.current_screen {
         CurrentScreen::Menu => {
@@ -43,7 +54,7 @@ pub fn ui_builder(f: &mut Frame, app: &mut App) {
             let mut menu_items = Vec::<ListItem>::new();
 
             for item in app.main_menu_items.iter() {
-                menu_items.push(ListItem::new(Text::from(item.to_owned())).white());
+                menu_items.push(ListItem::new(Text::from(item.title())).white());
             }
 
             f.render_stateful_widget({
@@ -67,63 +78,255 @@ pub fn ui_builder(f: &mut Frame, app: &mut App) {
         CurrentScreen::Leaderboard => {
             f.render_widget(create_header("Leaderboard"), rects[0]);
 
+            let footer_rects = Layout::horizontal([
+                Constraint::Percentage(35),
+                Constraint::Percentage(65),
+            ]).split(rects[3]);
+
+            if let Some(path) = &app.leaderboard.import_path_input {
+                f.render_widget(Span::from(format!("Import from file: {path}"))
+                                    .add_modifier(Modifier::BOLD)
+                                    .fg(Color::LightGreen), rects[1]);
+            } else if let Some(status) = &app.leaderboard.status {
+                f.render_widget(Span::from(status.to_owned()).fg(Color::LightGreen), rects[1]);
+            }
+
+            render_leaderboard_table(f, rects[2], &app.leaderboard.scores, &mut app.leaderboard.table_state);
+
+            f.render_widget(create_footer_left_part(app), footer_rects[0]);
+            f.render_widget(create_footer_navigation("(r)ange (m)ode (d)aily (s)ort (e)xport (i)mport (⏎)replay (q)"),
+                            footer_rects[1]);
+
+            if app.leaderboard.is_export_popup {
+                let block = Block::bordered()
+                    .title_top(Line::from("Export").centered().on_blue().white())
+                    .blue();
+
+                let area = centered_rect(60, 16, f.size());
+                let text_area = block.inner(area);
+                let content = Paragraph::new("(c)sv | (m)arkdown | (j)son | (h)tml | (Esc) cancel")
+                    .centered()
+                    .white();
+
+                f.render_widget(Clear, area);
+                f.render_widget(block, area);
+                f.render_widget(content, text_area);
+            }
+        },
+        CurrentScreen::Replay => {
+            let Some(replay) = &app.replay else {
+                return;
+            };
+
+            let score = &replay.score;
+            f.render_widget(create_header(&format!("Replay: {} {}{}", score.name, score.number_range,
+                 if score.is_hard_mode { " [H]" } else { "" })), rects[0]);
+
+            f.render_widget({
+                Span::from(format!("Started at {}", score.started_at.format("%Y-%m-%d %H:%M:%S")))
+                    .fg(Color::DarkGray)
+            }, rects[1]);
+
+            render_replay(f, rects[2], replay);
+
             let footer_rects = Layout::horizontal([
                 Constraint::Percentage(50),
                 Constraint::Percentage(50),
             ]).split(rects[3]);
 
-            let scores = load_scores();
+            f.render_widget(create_footer_left_part(app), footer_rects[0]);
+            f.render_widget(create_footer_navigation("(←/→) step | (Space) play | (q) back"), footer_rects[1]);
+        },
+        CurrentScreen::Profiles => {
+            f.render_widget(create_header("Players"), rects[0]);
+
+            let edit_line = match app.profiles_info.edit {
+                Some(ProfileEdit::New) => Some(format!("New player name: {}", app.profiles_info.input)),
+                Some(ProfileEdit::Rename) => Some(format!("Rename to: {}", app.profiles_info.input)),
+                None => None,
+            };
+
+            f.render_widget({
+                let mut spans = Vec::new();
+
+                if let Some(edit_line) = edit_line {
+                    spans.push(Span::from(edit_line).add_modifier(Modifier::BOLD).fg(Color::LightGreen));
+                }
+                if let Some(error) = &app.profiles_info.error {
+                    spans.push(Span::from(format!("  {error}")).fg(Color::LightRed));
+                }
+
+                Line::from(spans)
+            }, rects[1]);
+
+            render_profiles(f, rects[2], app);
+
+            let navigation = match app.profiles_info.edit {
+                Some(_) => "(Enter) save | (Esc) cancel",
+                None => "(Enter) select | (n)ew | (r)ename | (q) back",
+            };
+            f.render_widget(create_footer_navigation(navigation), rects[3]);
+        },
+        CurrentScreen::HotSeatSetup => {
+            f.render_widget(create_header("Hot-seat game"), rects[0]);
+
+            f.render_widget({
+                let mut spans = vec![
+                    Span::from(format!("Add player: {}", app.hot_seat_setup.input))
+                        .add_modifier(Modifier::BOLD)
+                        .fg(Color::LightGreen),
+                ];
+
+                if let Some(error) = &app.hot_seat_setup.error {
+                    spans.push(Span::from(format!("  {error}")).fg(Color::LightRed));
+                }
+
+                Line::from(spans)
+            }, rects[1]);
+
+            render_hot_seat_setup(f, rects[2], &app.hot_seat_setup);
+
+            f.render_widget(create_footer_navigation("(Enter) add player, start when empty | (Backspace) remove | (Esc) back"),
+                            rects[3]);
+        },
+        CurrentScreen::RaceLobby => {
+            let Some(race) = &app.race else {
+                return;
+            };
+
+            f.render_widget(create_header("Waiting for the race to start"), rects[0]);
+
+            f.render_widget({
+                match (&race.status, race.host_port) {
+                    (Some(status), _) => Span::from(status.to_owned()).fg(Color::LightRed),
+                    (None, Some(port)) => Span::from(format!("Hosting on port {port}")).fg(Color::LightGreen),
+                    (None, None) => Span::from("Connected to the host").fg(Color::LightGreen),
+                }
+            }, rects[1]);
+
+            render_race_lobby(f, rects[2], race);
+
+            f.render_widget(create_footer_navigation("(q) leave"), rects[3]);
+        },
+        CurrentScreen::CustomGame => {
+            f.render_widget(create_header("Custom game"), rects[0]);
+
+            if let Some(error) = &app.custom_game_info.error {
+                f.render_widget({
+                    Span::from(error.to_owned())
+                        .add_modifier(Modifier::BOLD)
+                        .fg(Color::LightRed)
+                }, rects[1]);
+            }
 
-            render_leaderboard_table(f, rects[2], &scores);
+            render_custom_game(f, rects[2], app);
 
-            f.render_widget(create_footer_left_part(&app), footer_rects[0]);
-            f.render_widget(create_footer_navigation("(q) to back to menu"), footer_rects[1]);
+            f.render_widget(create_footer_navigation("(↑/↓) select | (Space) toggle | (Enter) start | (Esc) back"), rects[3]);
         },
         CurrentScreen::Game => {
-            let default_header = format!("Guess the number {}-{}{}!", app.game_info.min_number, app.game_info.max_number,
-                 if app.game_info.is_hard_mode { " [H]" } else { "" });
+            let default_header = format!("Guess the number {}-{}{}!", app.game.info.min_number, app.game.info.max_number,
+                 if app.game.info.rules.is_hard_mode { " [H]" } else { "" });
 
-            f.render_widget(create_header(match app.game_info.current_guess_response.len() {
+            f.render_widget(create_header(match app.guess_response.len() {
                 0 => default_header.as_str(),
-                _ => app.game_info.current_guess_response.as_str(),
+                _ => app.guess_response.as_str(),
             }), rects[0]);
 
-            let guess_choice = format!("Enter your guess: {}", app.user_input_info.input);
+            let guess_choice = match app.game.current_player() {
+                Some(player) => format!("{player}'s turn, enter your guess: {}", app.user_input_info.input),
+                None => format!("Enter your guess: {}", app.user_input_info.input),
+            };
             let name_choice = format!("Enter your name: {}", app.user_input_info.input);
 
             f.render_widget({
-                let text = match app.game_info.is_game_over {
-                    true => name_choice.as_str(),
-                    false => guess_choice.as_str(),
+                let text = match app.mode {
+                    UserInputMode::InputNumber => guess_choice.as_str(),
+                    UserInputMode::InputName => name_choice.as_str(),
+                    UserInputMode::GameOver => "Press (Enter) to return to menu",
                 };
 
-                Span::from(text)
-                    .add_modifier(Modifier::BOLD)
-                    .fg(Color::LightGreen)
+                let mut spans = vec![
+                    Span::from(text)
+                        .add_modifier(Modifier::BOLD)
+                        .fg(Color::LightGreen),
+                ];
+
+                if let Some(error) = &app.guess_error {
+                    spans.push(Span::from(format!("  {error}")).fg(Color::LightRed));
+                }
+                if let Some(status) = app.race.as_ref().and_then(|x| x.status.as_ref()) {
+                    spans.push(Span::from(format!("  {status}")).fg(Color::LightRed));
+                }
+
+                Line::from(spans)
             }, rects[1]);
 
-            if !app.game_info.is_hard_mode {
+            let is_hot_seat = !app.game.info.players.is_empty();
+            let side_rects = Layout::horizontal([
+                Constraint::Percentage(50),
+                Constraint::Percentage(50),
+            ]).split(rects[2]);
+
+            let history_rect = match &app.race {
+                Some(race) => {
+                    render_race_standings(f, side_rects[1], race);
+                    side_rects[0]
+                }
+                None if is_hot_seat => {
+                    render_hot_seat_players(f, side_rects[1], &app.game);
+                    side_rects[0]
+                }
+                None => rects[2],
+            };
+
+            if !app.game.info.rules.is_hard_mode {
                 f.render_widget({
                     let mut list_items = Vec::<ListItem>::new();
 
-                    for item in app.user_input_history.iter() {
-                        list_items.push(ListItem::new(Line::from(Span::styled(
-                            format!("{}", item.user_value),
-                            Style::default().fg(Color::DarkGray),
-                        ))));
+                    let move_durations = app.game.move_durations();
+                    let hints = app.game.hints();
+
+                    for (i, (item, duration)) in app.game.history.iter().zip(move_durations).enumerate().rev() {
+                        let mut spans = Vec::new();
+
+                        // Everyone at the keyboard needs the hints of the others.
+                        if let Some(player) = app.game.player_of_move(i) {
+                            let hint = match hints[i].1 {
+                                GuessOutcome::TooLow => "higher",
+                                GuessOutcome::TooHigh => "lower",
+                                _ => "correct",
+                            };
+
+                            spans.push(Span::styled(format!("{player:<16}"), Style::default().fg(Color::Gray)));
+                            spans.push(Span::styled(format!("{:<12}{hint:<8}", item.user_value),
+                                                    Style::default().fg(Color::Yellow)));
+                        } else {
+                            spans.push(Span::styled(
+                                format!("{:<12}", item.user_value),
+                                Style::default().fg(Color::DarkGray),
+                            ));
+                        }
+
+                        spans.push(Span::styled(
+                            format!("+{}", format_duration_ms(duration.num_milliseconds())),
+                            Style::default().fg(Color::DarkGray).add_modifier(Modifier::DIM),
+                        ));
+
+                        list_items.push(ListItem::new(Line::from(spans)));
                     }
 
                     List::new(list_items)
-                }, rects[2]);
+                }, history_rect);
             }
 
             let footer_rects = Layout::horizontal([
-                Constraint::Percentage(50),
-                Constraint::Percentage(50),
+                Constraint::Percentage(65),
+                Constraint::Percentage(35),
             ]).split(rects[3]);
 
-            f.render_widget(create_footer_left_part(&app), footer_rects[0]);
-            f.render_widget(create_footer_navigation("(q) to end game"), footer_rects[1]);
+            f.render_widget(create_footer_left_part(app), footer_rects[0]);
+            let navigation = if app.race.is_some() { "(q) to leave the race" } else { "(h) hint | (q) to end game" };
+            f.render_widget(create_footer_navigation(navigation), footer_rects[1]);
 
             if app.quit_confirm_popup {
                 let block = Block::bordered()
@@ -142,4 +345,8 @@ pub fn ui_builder(f: &mut Frame, app: &mut App) {
             }
         }
     }
-}
\ No newline at end of file
+
+    if let Some(error) = &app.error_banner {
+        f.render_widget(create_error_banner(error), screen_rects[0]);
+    }
+}